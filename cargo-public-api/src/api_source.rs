@@ -1,12 +1,12 @@
 //! Contains various ways of obtaining the public API for crates.
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use rustdoc_json::BuildError;
 use std::path::{Path, PathBuf};
 
 use public_api::{PublicApi, MINIMUM_NIGHTLY_RUST_VERSION};
 
-use crate::{git_utils, Args, Subcommand};
+use crate::{git_utils, timed, Args, Subcommand};
 
 /// Represents some place from which a public API can be obtained.
 /// Examples: a published crate, a git commit, an existing file.
@@ -38,25 +38,97 @@ impl ApiSource for CurrentDir {
         public_api_for_current_dir(args)
     }
 }
-/// The API is obtained from a crate published to crates.io. This struct only
-/// contains the version. The name of the package is obtained via [`Args`].
-/// Either via `-p` or via `--manifest-path`.
+/// The API is obtained from a crate published to crates.io. Unless a name is
+/// given, the name of the package is obtained via [`Args`]. Either via `-p` or
+/// via `--manifest-path`.
 pub struct PublishedCrate {
+    name: Option<String>,
     version: Option<String>,
 }
 
 impl PublishedCrate {
     pub fn new(version: Option<&str>) -> Self {
         Self {
+            name: None,
             version: version.map(ToOwned::to_owned),
         }
     }
+
+    /// Creates a new instance from `crate` or `crate@version`.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let (name, version) = match spec.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (spec, None),
+        };
+        if name.is_empty() || version.is_some_and(str::is_empty) {
+            bail!("Expected `crate` or `crate@version` but got `{spec}`");
+        }
+        Ok(Self {
+            name: Some(name.to_owned()),
+            version: version.map(ToOwned::to_owned),
+        })
+    }
 }
 
 impl ApiSource for PublishedCrate {
     fn obtain_api(&self, args: &Args) -> Result<public_api::PublicApi> {
-        let rustdoc_json =
-            crate::published_crate::build_rustdoc_json(self.version.as_deref(), args)?;
+        let version = self.version.as_deref();
+        let rustdoc_json = match &self.name {
+            Some(name) => {
+                crate::published_crate::build_rustdoc_json_for_package(name, version, args)?
+            }
+            None => crate::published_crate::build_rustdoc_json(version, args)?,
+        };
+        public_api_from_rustdoc_json(rustdoc_json, args)
+    }
+}
+
+/// The API is obtained from rustdoc JSON for a published crate that docs.rs
+/// has built. Optionally falls back to building the rustdoc JSON from the
+/// crates.io source if docs.rs does not have it.
+pub struct DocsRs {
+    name: String,
+    version: String,
+    fallback: bool,
+}
+
+impl DocsRs {
+    /// Creates a new instance from `crate@version`.
+    pub fn new(spec: &str, fallback: bool) -> Result<Self> {
+        let Some((name, version)) = spec.split_once('@') else {
+            bail!("Expected `crate@version` after `--docs-rs` but got `{spec}`");
+        };
+        Ok(Self {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            fallback,
+        })
+    }
+}
+
+impl ApiSource for DocsRs {
+    fn obtain_api(&self, args: &Args) -> Result<PublicApi> {
+        let Self {
+            name,
+            version,
+            fallback,
+        } = self;
+        let download = || crate::docs_rs::download_rustdoc_json(name, version, args);
+        let rustdoc_json = match timed(args, "Downloading rustdoc JSON from docs.rs", download) {
+            Err(e)
+                if *fallback
+                    && matches!(
+                        e.downcast_ref::<crate::error::Error>(),
+                        Some(crate::error::Error::DocsRsJsonNotFound { .. })
+                    ) =>
+            {
+                if !args.quiet {
+                    eprintln!("docs.rs has no rustdoc JSON for `{name}@{version}`, building it from crates.io source instead");
+                }
+                crate::published_crate::build_rustdoc_json_for_package(name, Some(version), args)?
+            }
+            result => result?,
+        };
         public_api_from_rustdoc_json(rustdoc_json, args)
     }
 }
@@ -113,26 +185,61 @@ fn public_api_for_current_dir(args: &Args) -> Result<PublicApi> {
 
 /// Builds the rustdoc JSON for the library in the current working directory.
 /// Also see [`public_api_for_current_dir()`].
-fn rustdoc_json_for_current_dir(args: &Args) -> Result<PathBuf> {
+pub fn rustdoc_json_for_current_dir(args: &Args) -> Result<PathBuf> {
+    validate_package(args)?;
     let builder = builder_from_args(args);
-    build_rustdoc_json(builder)
+    build_rustdoc_json(builder, args)
+}
+
+/// Makes sure that the package given with `-p` is part of the workspace, so
+/// that we can list the packages that are, rather than failing the build with
+/// whatever error `cargo rustdoc` gives.
+fn validate_package(args: &Args) -> Result<()> {
+    let Some(package) = &args.package else {
+        return Ok(());
+    };
+
+    let metadata = cargo_metadata::MetadataCommand::new()
+        .manifest_path(&args.manifest_path)
+        .no_deps()
+        .exec()?;
+    let members = metadata
+        .workspace_packages()
+        .into_iter()
+        .map(|member| member.name.clone())
+        .collect::<Vec<_>>();
+
+    if members.contains(package) {
+        Ok(())
+    } else {
+        Err(crate::error::Error::PackageNotInWorkspace {
+            package: package.clone(),
+            members: members.join(", "),
+        }
+        .into())
+    }
 }
 
 /// Helper to build rustdoc JSON with a builder while also handling any virtual
 /// manifest errors.
-pub fn build_rustdoc_json(builder: rustdoc_json::Builder) -> Result<PathBuf> {
-    match builder.build() {
+pub fn build_rustdoc_json(builder: rustdoc_json::Builder, args: &Args) -> Result<PathBuf> {
+    match timed(args, "Building rustdoc JSON", || builder.build()) {
         Err(BuildError::VirtualManifest(manifest_path)) => virtual_manifest_error(&manifest_path),
         res => Ok(res?),
     }
 }
 
 fn public_api_builder_from_args(rustdoc_json: &Path, args: &Args) -> public_api::Builder {
-    public_api::Builder::from_rustdoc_json(rustdoc_json)
+    let mut builder = public_api::Builder::from_rustdoc_json(rustdoc_json)
         .debug_sorting(args.debug_sorting)
-        .omit_blanket_impls(args.omit_blanket_impls())
+        .blanket_impl_policy(args.blanket_impl_policy())
         .omit_auto_trait_impls(args.omit_auto_trait_impls())
         .omit_auto_derived_impls(args.omit_auto_derived_impls())
+        .line_filter(args.filter.clone());
+    if let Some(target) = &args.target {
+        builder = builder.target(target.clone());
+    }
+    builder
 }
 
 /// Creates a rustdoc JSON builder based on the args to this program.
@@ -140,6 +247,7 @@ pub fn builder_from_args(args: &Args) -> rustdoc_json::Builder {
     let mut builder = rustdoc_json::Builder::default()
         .manifest_path(&args.manifest_path)
         .verbose(args.verbose)
+        .quiet(args.quiet)
         .all_features(args.all_features)
         .no_default_features(args.no_default_features)
         .features(&args.features);
@@ -169,14 +277,16 @@ fn public_api_from_rustdoc_json(path: impl AsRef<Path>, args: &Args) -> Result<P
     let json_path = path.as_ref();
 
     if args.verbose {
-        println!("Processing {json_path:?}");
+        eprintln!("Processing {json_path:?}");
     }
 
-    let public_api = public_api_builder_from_args(json_path, args)
-        .build()
-        .with_context(|| {
-            format!(
-                "Failed to parse rustdoc JSON at {json_path:?}.
+    let builder = public_api_builder_from_args(json_path, args);
+    let public_api = timed(args, "Parsing and rendering rustdoc JSON", || {
+        builder.build()
+    })
+    .with_context(|| {
+        format!(
+            "Failed to parse rustdoc JSON at {json_path:?}.
 
 This version of `cargo public-api` requires at least:
 
@@ -194,12 +304,12 @@ to install the latest version with
 If the issue remains, please report at
 
     https://github.com/Enselic/cargo-public-api/issues",
-            )
-        })?;
+        )
+    })?;
 
     if args.verbose {
         public_api.missing_item_ids().for_each(|i| {
-            println!("NOTE: rustdoc JSON missing referenced item with ID \"{i}\"");
+            eprintln!("NOTE: rustdoc JSON missing referenced item with ID \"{i}\"");
         });
     }
 
@@ -54,6 +54,16 @@ impl Color {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
+#[value(rename_all = "lower")]
+pub enum Format {
+    /// One item per line, rendered as Rust code.
+    Plain,
+
+    /// One JSON object per item and line, with the fields `text`, `path`, `kind` and `tokens`.
+    Jsonl,
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ValueEnum)]
 #[value(rename_all = "kebab-case")]
 #[allow(clippy::enum_variant_names)] // We might add support for omitting other things in the future
@@ -70,6 +80,10 @@ pub enum Omit {
     /// Omit items that belong to Auto Derived Implementations such as `Clone`,
     /// `Debug`, and `Eq`.
     AutoDerivedImpls,
+
+    /// Omit items that belong to Blanket Implementations of other crates, but
+    /// keep those of the crate itself, such as `impl<T> MyExt for T`.
+    ExternalBlanketImpls,
 }
 
 #[cfg(test)]
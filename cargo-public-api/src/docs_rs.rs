@@ -0,0 +1,82 @@
+//! Downloads rustdoc JSON for published crates from docs.rs, so that users
+//! don't have to build old versions of a crate themselves.
+
+use crate::Args;
+use anyhow::{anyhow, Context, Result};
+use std::io::Read;
+use std::path::PathBuf;
+
+/// Downloads the rustdoc JSON for version `version` of crate `name` from
+/// docs.rs and returns the path to it. Fails with
+/// [`crate::error::Error::DocsRsJsonNotFound`] if docs.rs has no rustdoc JSON
+/// for the crate version.
+pub fn download_rustdoc_json(name: &str, version: &str, args: &Args) -> Result<PathBuf> {
+    // docs.rs serves the JSON compressed. We ask for gzip since that is the
+    // format we can decompress
+    let url = format!("https://docs.rs/crate/{name}/{version}/json.gz");
+    if args.verbose {
+        eprintln!("Downloading rustdoc JSON from {url:?}");
+    }
+
+    let (response_code, body) = http_get(&url)?;
+    if response_code == 404 {
+        return Err(anyhow!(crate::error::Error::DocsRsJsonNotFound {
+            name: name.to_owned(),
+            version: version.to_owned(),
+        }));
+    } else if response_code != 200 {
+        return Err(anyhow!(
+            "Downloading rustdoc JSON from {url:?} failed with HTTP status {response_code}"
+        ));
+    }
+
+    let mut rustdoc_json = String::new();
+    flate2::read::GzDecoder::new(body.as_slice())
+        .read_to_string(&mut rustdoc_json)
+        .with_context(|| format!("Could not decompress rustdoc JSON from {url:?}"))?;
+
+    // Fail early with a helpful message instead of with a parse error
+    public_api::check_format_version(&rustdoc_json).with_context(|| {
+        format!("The rustdoc JSON for `{name}@{version}` on docs.rs can't be used by this version of `cargo public-api`")
+    })?;
+
+    let path = json_path(args, name, version);
+    std::fs::create_dir_all(path.parent().expect("path has a parent"))?;
+    std::fs::write(&path, rustdoc_json)?;
+    Ok(path)
+}
+
+/// Where to store the downloaded rustdoc JSON. Same logic as for published
+/// crates that we build ourselves.
+fn json_path(args: &Args, name: &str, version: &str) -> PathBuf {
+    let mut path = if let Some(target_dir) = &args.target_dir {
+        target_dir.clone()
+    } else {
+        dirs::cache_dir().unwrap_or_else(std::env::temp_dir)
+    };
+
+    path.push("cargo-public-api");
+    path.push("docs-rs");
+    path.push(format!("{name}-{version}.json"));
+    path
+}
+
+/// Returns the HTTP response code and the body.
+fn http_get(url: &str) -> Result<(u32, Vec<u8>)> {
+    let mut body: Vec<u8> = vec![];
+
+    let mut curl = curl::easy::Easy::new();
+    curl.url(url)?;
+    curl.follow_location(true)?;
+
+    {
+        let mut transfer = curl.transfer();
+        transfer.write_function(|data| {
+            body.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()?;
+    }
+
+    Ok((curl.response_code()?, body))
+}
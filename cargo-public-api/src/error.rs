@@ -4,6 +4,12 @@ use public_api::{diff::ChangedPublicItem, PublicItem};
 pub enum Error {
     #[error("The API diff is not allowed as per --deny: {0}")]
     DiffDenied(Violations),
+
+    #[error("docs.rs has no rustdoc JSON for `{name}@{version}`, probably because it was built before docs.rs started to provide rustdoc JSON. Use `--docs-rs-fallback` to build it from crates.io source instead")]
+    DocsRsJsonNotFound { name: String, version: String },
+
+    #[error("Package `{package}` is not in the workspace. Packages in the workspace: {members}")]
+    PackageNotInWorkspace { package: String, members: String },
 }
 
 #[derive(Debug)]
@@ -0,0 +1,47 @@
+use std::io::{Result, Write};
+
+use public_api::{tokens::Token, ItemKind, PublicItem};
+use serde::Serialize;
+
+/// Prints items in the [JSON Lines](https://jsonlines.org) format, i.e. one
+/// JSON object per line. Allows consumers to process one item at a time, e.g.
+/// with `jq -c`.
+pub struct JsonLines;
+
+/// What is printed for each item. Only contains what the item looks like, and
+/// not e.g. how it is sorted, so that the format stays stable when
+/// `public-api` internals change.
+#[derive(Serialize)]
+struct Line<'a> {
+    /// The item as printed with `--format=plain`, e.g. `pub fn krate::f()`
+    text: String,
+
+    /// The path of the item, e.g. `["krate", "f"]`
+    path: Vec<&'a str>,
+
+    /// The kind of the item, e.g. `Function`
+    kind: ItemKind,
+
+    /// The item as a stream of tokens, e.g. `{"Qualifier": "pub"}`, for
+    /// syntax highlighting
+    tokens: Vec<&'a Token>,
+}
+
+impl JsonLines {
+    pub fn print_items<'a>(
+        w: &mut dyn Write,
+        items: impl Iterator<Item = &'a PublicItem>,
+    ) -> Result<()> {
+        for item in items {
+            let line = Line {
+                text: item.to_string(),
+                path: item.path().collect(),
+                kind: item.kind(),
+                tokens: item.tokens().collect(),
+            };
+            writeln!(w, "{}", serde_json::to_string(&line)?)?;
+        }
+
+        Ok(())
+    }
+}
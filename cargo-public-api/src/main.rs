@@ -1,23 +1,30 @@
 // deny in CI, only warn here
 #![warn(clippy::all)]
 
+use std::collections::HashMap;
 use std::ffi::OsString;
-use std::io::stdout;
+use std::io::{stdout, Write};
 use std::path::{Path, PathBuf};
 
-use anyhow::{anyhow, bail, Result};
-use api_source::{ApiSource, Commit, CurrentDir, PublishedCrate, RustdocJson};
-use arg_types::{Color, DenyMethod, Omit};
+use anyhow::{anyhow, bail, Context, Result};
+use api_source::{ApiSource, Commit, CurrentDir, DocsRs, PublishedCrate, RustdocJson};
+use arg_types::{Color, DenyMethod, Format, Omit};
 use git_utils::current_branch_or_commit;
+use json_lines::JsonLines;
 use plain::Plain;
-use public_api::diff::PublicApiDiff;
+use public_api::{
+    diff::{recommend_version, PublicApiDiff, SemverImpact},
+    BlanketImplPolicy, PublicApi, PublicItem,
+};
 
 use clap::{CommandFactory, Parser};
 
 mod api_source;
 mod arg_types;
+mod docs_rs;
 mod error;
 mod git_utils;
+mod json_lines;
 mod plain;
 mod published_crate;
 mod toolchain;
@@ -55,6 +62,15 @@ pub struct Args {
     #[arg(long, value_enum, value_delimiter = ',')]
     omit: Option<Vec<Omit>>,
 
+    /// Only list or diff items whose rendered line matches REGEX.
+    ///
+    /// The regex is matched against items as they are printed with
+    /// `--format=plain`, without colors. For example, `--filter '^pub fn '`
+    /// only includes functions. When diffing, both public APIs are filtered
+    /// before they are diffed.
+    #[arg(long, value_name = "REGEX")]
+    filter: Option<regex::Regex>,
+
     /// Space or comma separated list of features to activate
     #[arg(long, short = 'F', num_args = 1..)]
     features: Vec<String>,
@@ -78,6 +94,19 @@ pub struct Args {
     #[arg(long, value_enum)]
     color: Option<Option<Color>>,
 
+    /// How to print the public API.
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+
+    /// Write the rustdoc JSON to PATH instead of printing the public API.
+    ///
+    /// The rustdoc JSON is built the same way as when listing the public API,
+    /// but no items are printed. The file can later be listed or diffed, e.g.
+    /// with `cargo public-api diff old.json new.json`, without having to build
+    /// it again.
+    #[arg(long, value_name = "PATH", conflicts_with = "rustdoc_json")]
+    dump_json: Option<PathBuf>,
+
     /// List the public API based on the given rustdoc JSON file.
     ///
     /// Example:
@@ -93,13 +122,21 @@ pub struct Args {
     #[arg(long, value_name = "RUSTDOC_JSON_PATH", hide = true)]
     rustdoc_json: Option<String>,
 
-    /// Show detailed info about processing.
+    /// Show each processing phase and how long it took.
     ///
-    /// For debugging purposes. The output is not stable and can change across
-    /// patch versions.
-    #[arg(long, hide = true)]
+    /// Useful if e.g. building rustdoc JSON for a large crate takes a long
+    /// time. Phases and timings are printed to stderr. The output is not stable
+    /// and can change across patch versions.
+    #[arg(long, conflicts_with = "quiet")]
     verbose: bool,
 
+    /// Only print the public API or the diff.
+    ///
+    /// Suppresses warnings and progress output from both this tool and `cargo`.
+    /// Errors are still printed.
+    #[arg(long)]
+    quiet: bool,
+
     /// Show the hidden "sorting prefix" that makes items nicely grouped
     ///
     /// Only intended for debugging this tool.
@@ -145,11 +182,69 @@ struct DiffArgs {
     #[arg(long)]
     force: bool,
 
+    /// Fail if the two public APIs belong to crates with different names.
+    ///
+    /// By default, only a warning is printed in that case.
+    #[arg(long)]
+    strict: bool,
+
+    /// Shorten changed items by eliding what the old and new item start and end with.
+    ///
+    /// The name of the item is always shown. For example, a function that got a
+    /// new arg is shown as `...function(v1_param: Struct, v2_param: usize)`, and
+    /// a function with a changed return type is shown as `...function(x: u8) -> u64`.
+    #[arg(long)]
+    elide_common_tokens: bool,
+
+    /// Diff against rustdoc JSON for `CRATE@VERSION` from docs.rs instead of
+    /// building it.
+    ///
+    /// For example, `--docs-rs regex@1.10.2` diffs the public API of the
+    /// working tree against the public API of regex 1.10.2 that docs.rs has
+    /// built. Only crate versions that docs.rs has rustdoc JSON for can be
+    /// used, and the format of the JSON must be supported by this version of
+    /// `cargo public-api`.
+    #[arg(long, value_name = "CRATE@VERSION")]
+    docs_rs: Option<String>,
+
+    /// If docs.rs has no rustdoc JSON for `--docs-rs`, build it from crates.io
+    /// source instead.
+    #[arg(long, requires = "docs_rs")]
+    docs_rs_fallback: bool,
+
+    /// Recommend the version to release next, given the current VERSION of the
+    /// crate.
+    ///
+    /// After the diff, prints e.g. `Detected MAJOR changes; recommend bumping
+    /// 0.2.3 → 0.3.0`. Before 1.0.0, breaking changes bump the MINOR version,
+    /// like Cargo expects.
+    #[arg(long, value_name = "VERSION")]
+    bump_from: Option<semver::Version>,
+
     /// What to diff. See `cargo public-api diff --help` for examples and more
     /// info.
     args: Vec<String>,
 }
 
+/// The subcommand used for printing the public API of a published crate.
+#[derive(Parser, Debug)]
+struct ShowArgs {
+    /// The crate to show, as `crate` or `crate@version`.
+    spec: String,
+}
+
+/// The subcommand used for listing items added since a baseline.
+#[derive(Parser, Debug)]
+struct NewSinceArgs {
+    /// Also list items of auto-derived impls, blanket impls and auto trait
+    /// impls.
+    #[arg(long)]
+    include_impls: bool,
+
+    /// File with the baseline public API, as printed by `cargo public-api`.
+    baseline: PathBuf,
+}
+
 #[derive(clap::Subcommand, Debug)]
 enum Subcommand {
     /// Diff the public API against a published version of the crate, or between commits.
@@ -193,6 +288,10 @@ enum Subcommand {
     ///
     ///     cargo public-api diff first.json second.json
     ///
+    /// Diffing the current working tree against rustdoc JSON from docs.rs:
+    ///
+    ///     cargo public-api diff --docs-rs regex@1.10.2
+    ///
     ///
     /// HOW COMMIT DIFFING WORKS:
     /// =========================
@@ -216,6 +315,32 @@ enum Subcommand {
     #[clap(verbatim_doc_comment)]
     Diff(DiffArgs),
 
+    /// List public items added since a baseline, as Markdown for a changelog.
+    ///
+    /// The baseline is a file with the output of `cargo public-api`, e.g. a
+    /// `public-api.txt` that is kept in the repo and updated with each release.
+    /// Items that are not in the baseline are printed as a bullet list, ready
+    /// to paste into the "Added" section of a changelog. Items of auto-derived
+    /// impls, blanket impls and auto trait impls are omitted unless
+    /// `--include-impls` is used.
+    ///
+    /// Example:
+    ///
+    ///     cargo public-api new-since public-api.txt
+    NewSince(NewSinceArgs),
+
+    /// Print the public API of a crate published to crates.io.
+    ///
+    /// The crate is downloaded and its rustdoc JSON is built in a cache dir,
+    /// so repeated invocations for the same version are fast. Without a
+    /// version, the latest version is used. The crate does not need to be part
+    /// of the current workspace.
+    ///
+    /// Example:
+    ///
+    ///     cargo public-api show serde@1.0.193
+    Show(ShowArgs),
+
     /// Generate completion scripts for many different shells.
     ///
     /// Example on how to generate and install the completion script for zsh:
@@ -248,6 +373,15 @@ enum MainTask {
         old_api: Box<dyn ApiSource>,
         new_api: Box<dyn ApiSource>,
     },
+    /// Print the items of the public API of a crate that are not in a baseline.
+    PrintNewSince {
+        baseline: PathBuf,
+        api: Box<dyn ApiSource>,
+    },
+    /// Build rustdoc JSON and write it to a file without printing anything.
+    DumpRustdocJson {
+        destination: PathBuf,
+    },
     GenerateShellCompletionScript(clap_complete_command::Shell),
 }
 
@@ -297,6 +431,10 @@ fn main_() -> Result<()> {
             new_api.as_ref(),
             &mut final_actions,
         ),
+        MainTask::PrintNewSince { baseline, api } => {
+            print_new_since(&args, &baseline, api.as_ref())
+        }
+        MainTask::DumpRustdocJson { destination } => dump_rustdoc_json(&args, &destination),
         MainTask::GenerateShellCompletionScript(shell) => {
             shell.generate(
                 &mut Args::command().bin_name("cargo-public-api"),
@@ -316,8 +454,19 @@ fn main_() -> Result<()> {
 }
 
 fn main_task(args: &Args) -> Result<MainTask> {
+    if args.dump_json.is_some() && args.subcommand.is_some() {
+        bail!("`--dump-json` can't be combined with a subcommand");
+    }
+
     match &args.subcommand {
         Some(Subcommand::Diff(diff_args)) => main_task_from_diff_args(args, diff_args),
+        Some(Subcommand::NewSince(new_since_args)) => Ok(MainTask::PrintNewSince {
+            baseline: new_since_args.baseline.clone(),
+            api: current_api_source(args),
+        }),
+        Some(Subcommand::Show(show_args)) => Ok(MainTask::print_list(
+            PublishedCrate::from_spec(&show_args.spec)?.boxed(),
+        )),
         Some(Subcommand::Completions { shell }) => {
             Ok(MainTask::GenerateShellCompletionScript(*shell))
         }
@@ -326,10 +475,21 @@ fn main_task(args: &Args) -> Result<MainTask> {
 }
 
 fn main_task_from_args(args: &Args) -> MainTask {
+    if let Some(destination) = &args.dump_json {
+        MainTask::DumpRustdocJson {
+            destination: destination.clone(),
+        }
+    } else {
+        MainTask::print_list(current_api_source(args))
+    }
+}
+
+/// The public API to list when there is nothing to diff against.
+fn current_api_source(args: &Args) -> Box<dyn ApiSource> {
     if let Some(rustdoc_json) = &args.rustdoc_json {
-        MainTask::print_list(RustdocJson::new(rustdoc_json.into()).boxed())
+        RustdocJson::new(rustdoc_json.into()).boxed()
     } else {
-        MainTask::print_list(CurrentDir.boxed())
+        CurrentDir.boxed()
     }
 }
 
@@ -352,6 +512,16 @@ fn main_task_from_diff_args(args: &Args, diff_args: &DiffArgs) -> Result<MainTas
         )
     }
 
+    if let Some(spec) = &diff_args.docs_rs {
+        if !diff_args.args.is_empty() {
+            bail!("`--docs-rs` can't be combined with other things to diff");
+        }
+        return Ok(MainTask::print_diff(
+            DocsRs::new(spec, diff_args.docs_rs_fallback)?.boxed(),
+            CurrentDir.boxed(),
+        ));
+    }
+
     let first_arg = diff_args.args.get(0);
     let second_arg = diff_args.args.get(1);
 
@@ -409,8 +579,24 @@ fn check_diff(deny: &[DenyMethod], diff: &PublicApiDiff) -> Result<()> {
     }
 }
 
+/// Builds rustdoc JSON for the current dir and copies it to `destination`, so
+/// that it can be passed to e.g. `cargo public-api diff` later.
+fn dump_rustdoc_json(args: &Args, destination: &Path) -> Result<()> {
+    let json_path = api_source::rustdoc_json_for_current_dir(args)?;
+    std::fs::copy(&json_path, destination)
+        .with_context(|| format!("Failed to write rustdoc JSON to {destination:?}"))?;
+    if !args.quiet {
+        eprintln!("Wrote rustdoc JSON to {destination:?}");
+    }
+    Ok(())
+}
+
 fn print_public_items(args: &Args, public_api: &dyn ApiSource) -> Result<()> {
-    Plain::print_items(&mut stdout(), args, public_api.obtain_api(args)?.items())?;
+    let public_api = public_api.obtain_api(args)?;
+    match args.format {
+        Format::Plain => Plain::print_items(&mut stdout(), args, public_api.items())?,
+        Format::Jsonl => JsonLines::print_items(&mut stdout(), public_api.items())?,
+    }
 
     Ok(())
 }
@@ -428,12 +614,31 @@ fn print_diff(
         }
     }
 
+    if args.format != Format::Plain {
+        bail!("Diffs can currently only be printed with `--format=plain`");
+    }
+
     let old = old.obtain_api(args)?;
     let new = new.obtain_api(args)?;
-    let diff = PublicApiDiff::between(old, new);
+    let diff = if args.diff_args().is_some_and(|a| a.strict) {
+        timed(args, "Diffing public APIs", || {
+            PublicApiDiff::try_between(old, new)
+        })?
+    } else {
+        if !args.quiet {
+            warn_if_crate_names_differ(&old, &new);
+        }
+        timed(args, "Diffing public APIs", || {
+            PublicApiDiff::between(old, new)
+        })
+    };
 
     Plain::print_diff(&mut stdout(), args, &diff)?;
 
+    if let Some(current_version) = args.diff_args().and_then(|a| a.bump_from.as_ref()) {
+        print_version_recommendation(current_version, &diff)?;
+    }
+
     if let Some(Some(deny)) = args.diff_args().map(|a| &a.deny) {
         final_actions.push(check_diff(deny, diff));
     }
@@ -441,6 +646,97 @@ fn print_diff(
     Ok(())
 }
 
+/// Tells the user what version to release next, so they don't have to apply
+/// the semver rules to the diff themselves.
+fn print_version_recommendation(
+    current_version: &semver::Version,
+    diff: &PublicApiDiff,
+) -> Result<()> {
+    let detected = match diff.semver_impact() {
+        SemverImpact::Major => "Detected MAJOR changes",
+        SemverImpact::Minor => "Detected MINOR changes",
+        SemverImpact::Patch => "Detected no public API changes",
+    };
+    let current_version = current_version.to_string();
+    let next_version = recommend_version(&current_version, diff);
+    writeln!(
+        stdout(),
+        "{detected}; recommend bumping {current_version} → {next_version}"
+    )?;
+    Ok(())
+}
+
+fn print_new_since(args: &Args, baseline: &Path, api: &dyn ApiSource) -> Result<()> {
+    if args.format != Format::Plain {
+        bail!("New items can currently only be printed with `--format=plain`");
+    }
+
+    let baseline = std::fs::read_to_string(baseline)
+        .with_context(|| format!("Failed to read baseline {baseline:?}"))?;
+    let public_api = api.obtain_api(args)?;
+
+    let mut stdout = stdout();
+    for item in items_not_in_baseline(&baseline, public_api.items()) {
+        writeln!(stdout, "- `{item}`")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the items that are not in `baseline`, which is the output of
+/// `cargo public-api`, i.e. one rendered item per line. The baseline is only
+/// text, so items are compared by how they render. Items that render the same
+/// are counted, so that an added item is found even if an identical item
+/// already exists.
+fn items_not_in_baseline<'a>(
+    baseline: &str,
+    items: impl Iterator<Item = &'a PublicItem>,
+) -> Vec<&'a PublicItem> {
+    let mut baseline_counts: HashMap<&str, usize> = HashMap::new();
+    for line in baseline.lines() {
+        *baseline_counts.entry(line).or_default() += 1;
+    }
+
+    items
+        .filter(
+            |item| match baseline_counts.get_mut(item.to_string().as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            },
+        )
+        .collect()
+}
+
+/// Runs `f` and, if `--verbose` was passed, prints to stderr when `phase`
+/// starts and how long it took. Lets users see what is going on when e.g.
+/// building rustdoc JSON takes a long time.
+pub fn timed<T>(args: &Args, phase: &str, f: impl FnOnce() -> T) -> T {
+    if !args.verbose {
+        return f();
+    }
+
+    eprintln!("{phase} ...");
+    let start = std::time::Instant::now();
+    let result = f();
+    eprintln!("{phase} took {:.2?}", start.elapsed());
+    result
+}
+
+/// Diffing two different crates is most likely a mistake, such as passing the
+/// wrong rustdoc JSON file, so let the user know.
+fn warn_if_crate_names_differ(old: &PublicApi, new: &PublicApi) {
+    if let (Some(old_name), Some(new_name)) = (old.crate_name(), new.crate_name()) {
+        if old_name != new_name {
+            eprintln!(
+                "Warning: Diffing public API of crate `{old_name}` against crate `{new_name}`"
+            );
+        }
+    }
+}
+
 impl MainTask {
     fn print_list(api: Box<dyn ApiSource>) -> MainTask {
         Self::PrintList { api }
@@ -455,8 +751,10 @@ impl MainTask {
             MainTask::PrintDiff { old_api, new_api } => {
                 old_api.changes_commit() || new_api.changes_commit()
             }
-            MainTask::PrintList { api } => api.changes_commit(),
-            MainTask::GenerateShellCompletionScript(_) => false,
+            MainTask::PrintList { api } | MainTask::PrintNewSince { api, .. } => {
+                api.changes_commit()
+            }
+            MainTask::DumpRustdocJson { .. } | MainTask::GenerateShellCompletionScript(_) => false,
         }
     }
 }
@@ -476,8 +774,14 @@ impl Action {
 }
 
 impl Args {
-    fn omit_blanket_impls(&self) -> bool {
-        self.omits(Omit::BlanketImpls)
+    fn blanket_impl_policy(&self) -> BlanketImplPolicy {
+        if self.omits(Omit::BlanketImpls) {
+            BlanketImplPolicy::None
+        } else if self.omits(Omit::ExternalBlanketImpls) {
+            BlanketImplPolicy::LocalOnly
+        } else {
+            BlanketImplPolicy::All
+        }
     }
 
     fn omit_auto_trait_impls(&self) -> bool {
@@ -521,6 +825,7 @@ fn get_args() -> Args {
     let mut args = Args::parse_from(args_os);
     resolve_toolchain(&mut args);
     resolve_simplified(&mut args);
+    resolve_new_since(&mut args);
     args
 }
 
@@ -543,7 +848,8 @@ fn subcommand_name(bin: OsString) -> Option<OsString> {
 /// Check if using a stable compiler, and use nightly if it is.
 fn resolve_toolchain(args: &mut Args) {
     if toolchain::is_probably_stable(args.toolchain.as_deref()) {
-        if let Some(toolchain) = args.toolchain.clone().or_else(toolchain::from_rustup) {
+        let toolchain = args.toolchain.clone().or_else(toolchain::from_rustup);
+        if let Some(toolchain) = toolchain.filter(|_| !args.quiet) {
             eprintln!("Warning: using the `{toolchain}` toolchain for gathering the public api is not possible, switching to `nightly`");
         }
         args.toolchain = Some("nightly".to_owned());
@@ -572,6 +878,20 @@ fn resolve_simplified(args: &mut Args) {
     }
 }
 
+/// Makes `new-since` omit items of impls, unless `--include-impls` is used.
+/// Such items are rarely worth mentioning in a changelog.
+fn resolve_new_since(args: &mut Args) {
+    if let Some(Subcommand::NewSince(new_since_args)) = &args.subcommand {
+        if !new_since_args.include_impls {
+            args.omit.get_or_insert_with(Vec::new).extend([
+                Omit::BlanketImpls,
+                Omit::AutoTraitImpls,
+                Omit::AutoDerivedImpls,
+            ]);
+        }
+    }
+}
+
 fn is_json_file(file_name: impl AsRef<str>) -> bool {
     Path::extension(Path::new(file_name.as_ref())).map_or(false, |a| a.eq_ignore_ascii_case("json"))
 }
@@ -22,6 +22,7 @@ impl Plain {
 
     pub fn print_diff(w: &mut dyn Write, args: &Args, diff: &PublicApiDiff) -> Result<()> {
         let use_color = color_active(args.color);
+        let elide_common_tokens = args.diff_args().is_some_and(|a| a.elide_common_tokens);
 
         print_items_with_header(
             w,
@@ -41,18 +42,33 @@ impl Plain {
             "Changed items in the public API",
             &diff.changed,
             |w, changed_item| {
+                let old_tokens: Vec<&Token> = changed_item.old.tokens().collect();
+                let new_tokens: Vec<&Token> = changed_item.new.tokens().collect();
+                let (prefix_len, suffix_len) = if elide_common_tokens {
+                    elidable_lens(&old_tokens, &new_tokens)
+                } else {
+                    (0, 0)
+                };
+                let old_tokens = &old_tokens[prefix_len..old_tokens.len() - suffix_len];
+                let new_tokens = &new_tokens[prefix_len..new_tokens.len() - suffix_len];
+                let prefix = if prefix_len > 0 { "..." } else { "" };
+                let suffix = if suffix_len > 0 { "..." } else { "" };
+
                 if use_color {
-                    let old_tokens: Vec<&Token> = changed_item.old.tokens().collect();
-                    let new_tokens: Vec<&Token> = changed_item.new.tokens().collect();
-                    let diff_slice = diff::slice(old_tokens.as_slice(), new_tokens.as_slice());
+                    let diff_slice = diff::slice(old_tokens, new_tokens);
                     writeln!(
                         w,
-                        "-{}\n+{}",
+                        "-{prefix}{}{suffix}\n+{prefix}{}{suffix}",
                         color_item_with_diff(&diff_slice, true),
                         color_item_with_diff(&diff_slice, false),
                     )
                 } else {
-                    writeln!(w, "-{}\n+{}", changed_item.old, changed_item.new)
+                    writeln!(
+                        w,
+                        "-{prefix}{}{suffix}\n+{prefix}{}{suffix}",
+                        tokens_to_string(old_tokens),
+                        tokens_to_string(new_tokens),
+                    )
                 }
             },
         )?;
@@ -82,6 +98,60 @@ fn print_item(args: &Args, w: &mut dyn Write, item: &PublicItem) -> Result<()> {
     }
 }
 
+/// Returns how many leading and trailing tokens that are the same in `old` and
+/// `new` that can be left out when printing a changed item. So that it stays
+/// clear what item changed, we never elide the name of the item, i.e. a changed
+/// function is shown as `...function(v1_param: Struct, v2_param: usize)`. The
+/// name is the token right before the first symbol other than `::`, e.g. the
+/// `(` of a function or the `:` of a field. Items without such a name, e.g.
+/// impls, are not elided at all.
+///
+/// To keep some context, we do not elide beyond the first closing bracket at
+/// the end, so that a function with a changed parameter is shown as
+/// `...f(x: u64)...` rather than as `...f(x: u64...`.
+fn elidable_lens(old: &[&Token], new: &[&Token]) -> (usize, usize) {
+    let Some(name) = item_name_index(old) else {
+        return (0, 0);
+    };
+    let common_prefix_len = old.iter().zip(new).take_while(|(o, n)| o == n).count();
+    let prefix_len = if name <= common_prefix_len { name } else { 0 };
+
+    let (old, new) = (&old[prefix_len..], &new[prefix_len..]);
+    let common_suffix_len = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(o, n)| o == n)
+        .count();
+    let suffix_len = old[old.len() - common_suffix_len..]
+        .iter()
+        .position(|token| is_closing_bracket(token))
+        .map_or(0, |bracket| common_suffix_len - bracket - 1);
+
+    (prefix_len, suffix_len)
+}
+
+/// The index of the name of the item, see [`elidable_lens()`].
+fn item_name_index(tokens: &[&Token]) -> Option<usize> {
+    let name = tokens
+        .iter()
+        .position(|token| matches!(token, Token::Symbol(s) if s != "::"))?
+        .checked_sub(1)?;
+    matches!(
+        tokens[name],
+        Token::Function(_) | Token::Identifier(_) | Token::Type(_)
+    )
+    .then_some(name)
+}
+
+fn is_closing_bracket(token: &Token) -> bool {
+    matches!(token, Token::Symbol(s) if s.ends_with([')', '>', ']']) && s != "->")
+}
+
+fn tokens_to_string(tokens: &[&Token]) -> String {
+    tokens.iter().map(|t| t.text()).collect()
+}
+
 fn color_active(color: Option<Option<crate::arg_types::Color>>) -> bool {
     match color {
         // An explicit color was specified: `--color=...`
@@ -3,15 +3,25 @@
 //! project.
 
 use crate::vendor::crates_index;
-use crate::{Args, LATEST_VERSION_ARG};
+use crate::{Args, Subcommand, LATEST_VERSION_ARG};
 use anyhow::{anyhow, Context, Result};
 use crates_index::{Crate, Version};
 use std::path::PathBuf;
 
 pub fn build_rustdoc_json(version: Option<&str>, args: &Args) -> Result<PathBuf> {
     let package_name = package_name_from_args(args).ok_or_else(|| anyhow!("You must specify a package with either `-p package-name` or `--manifest-path path/to/Cargo.toml`"))?;
-    let crate_ = http_get_crate(&package_name, args.verbose)?;
-    let crate_version = get_crate_version(&crate_, version)?;
+    build_rustdoc_json_for_package(&package_name, version, args)
+}
+
+/// Like [`build_rustdoc_json()`], but for an explicitly given package instead
+/// of the package given by [`Args`].
+pub fn build_rustdoc_json_for_package(
+    package_name: &str,
+    version: Option<&str>,
+    args: &Args,
+) -> Result<PathBuf> {
+    let crate_ = http_get_crate(package_name, args.verbose)?;
+    let crate_version = get_crate_version(&crate_, version, args)?;
     let build_dir = build_dir(args, &crate_version);
     std::fs::create_dir_all(&build_dir)?;
 
@@ -37,22 +47,37 @@ pub fn build_rustdoc_json(version: Option<&str>, args: &Args) -> Result<PathBuf>
         .no_default_features(false)
         .manifest_path(manifest)
         .package(crate_version.name());
-    crate::api_source::build_rustdoc_json(builder)
+    crate::api_source::build_rustdoc_json(builder, args)
 }
 
-fn get_crate_version(crate_: &Crate, version: Option<&str>) -> Result<Version, anyhow::Error> {
+fn get_crate_version(
+    crate_: &Crate,
+    version: Option<&str>,
+    args: &Args,
+) -> Result<Version, anyhow::Error> {
     match version {
         Some(LATEST_VERSION_ARG) | None => {
-            let resolved = if version.is_none() {
-                "diff"
-            } else {
-                "diff latest"
-            };
             let crate_version = crate_.highest_version().clone();
-            eprintln!(
-                "Resolved `{resolved}` to `diff {}`",
-                crate_version.version()
-            );
+            if !args.quiet {
+                if matches!(args.subcommand, Some(Subcommand::Show(_))) {
+                    eprintln!(
+                        "Resolved `{}` to `{}@{}`",
+                        crate_.name(),
+                        crate_.name(),
+                        crate_version.version()
+                    );
+                } else {
+                    let resolved = if version.is_none() {
+                        "diff"
+                    } else {
+                        "diff latest"
+                    };
+                    eprintln!(
+                        "Resolved `{resolved}` to `diff {}`",
+                        crate_version.version()
+                    );
+                }
+            }
             Ok(crate_version)
         }
         Some(version) => crate_
@@ -57,6 +57,16 @@ fn list_public_items_omit_blanket_impls() {
         .success();
 }
 
+#[test]
+fn list_public_items_omit_external_blanket_impls() {
+    let mut cmd = TestCmd::as_subcommand_without_args().with_test_repo();
+    cmd.arg("--omit");
+    cmd.arg("external-blanket-impls");
+    cmd.assert()
+        .stdout_or_update("./expected-output/omit-external-blanket-impls.txt")
+        .success();
+}
+
 #[test]
 fn list_public_items_omit_auto_trait_impls_impls() {
     let mut cmd = TestCmd::as_subcommand_without_args().with_test_repo();
@@ -263,6 +273,20 @@ fn list_public_items_via_package_spec() {
         .success();
 }
 
+#[test]
+fn list_public_items_via_unknown_package_spec() {
+    let mut cmd = TestCmd::new().with_separate_target_dir();
+    cmd.current_dir("../test-apis/virtual-manifest");
+    cmd.arg("--package");
+    cmd.arg("no-such-crate");
+    cmd.assert()
+        .stdout("")
+        .stderr(contains(
+            "Package `no-such-crate` is not in the workspace. Packages in the workspace: specific-crate",
+        ))
+        .failure();
+}
+
 #[test]
 fn target_arg() {
     // A bit of a hack but similar to how rustc bootstrap script does it:
@@ -436,6 +460,122 @@ fn diff_with_invalid_published_crate_version() {
         .failure();
 }
 
+#[test]
+fn new_since_baseline() {
+    let baseline_dir = tempdir().unwrap();
+    let baseline = baseline_dir.path().join("public-api.txt");
+    std::fs::write(
+        &baseline,
+        "pub mod example_api\npub struct example_api::StructV2\npub example_api::StructV2::field: usize\n",
+    )
+    .unwrap();
+
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("new-since");
+    cmd.arg(&baseline);
+    cmd.assert()
+        .stdout_or_update("./expected-output/new_since_baseline.txt")
+        .success();
+}
+
+#[test]
+fn new_since_missing_baseline() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("new-since");
+    cmd.arg("does-not-exist.txt");
+    cmd.assert()
+        .stderr(contains("Failed to read baseline \"does-not-exist.txt\""))
+        .failure();
+}
+
+#[test]
+fn filter() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--filter");
+    cmd.arg("^pub fn ");
+    cmd.assert()
+        .stdout(
+            "pub fn example_api::Struct::fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result\n",
+        )
+        .success();
+}
+
+#[test]
+fn filter_diff() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--filter");
+    cmd.arg("struct");
+    cmd.arg("diff");
+    cmd.arg("v0.2.0..v0.3.0");
+    cmd.assert().stdout(contains("(none)").count(3)).success();
+}
+
+#[test]
+fn filter_with_invalid_regex() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--filter");
+    cmd.arg("(");
+    cmd.assert()
+        .stderr(contains("invalid value '(' for '--filter <REGEX>'"))
+        .failure();
+}
+
+#[test]
+fn dump_json() {
+    let json_dir = tempdir().unwrap();
+    let json = json_dir.path().join("out.json");
+
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--dump-json");
+    cmd.arg(&json);
+    cmd.assert()
+        .stdout("")
+        .stderr(contains("Wrote rustdoc JSON to"))
+        .success();
+
+    let mut cmd = TestCmd::new();
+    cmd.arg("--rustdoc-json");
+    cmd.arg(&json);
+    cmd.assert()
+        .stdout_or_update("./expected-output/test_repo_api_latest.txt")
+        .success();
+}
+
+#[test]
+fn dump_json_with_subcommand() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--dump-json");
+    cmd.arg("out.json");
+    cmd.arg("diff");
+    cmd.assert()
+        .stderr("Error: `--dump-json` can't be combined with a subcommand\n")
+        .failure();
+}
+
+#[test]
+fn diff_docs_rs_with_invalid_spec() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("diff");
+    cmd.arg("--docs-rs");
+    cmd.arg("example_api");
+    cmd.assert()
+        .stderr("Error: Expected `crate@version` after `--docs-rs` but got `example_api`\n")
+        .failure();
+}
+
+#[test]
+fn diff_docs_rs_without_rustdoc_json() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("diff");
+    cmd.arg("--docs-rs");
+    cmd.arg("this-create-has-not-been-published-and-never-will@0.1.0");
+    cmd.assert()
+        .stderr(contains(
+            "Error: docs.rs has no rustdoc JSON for `this-create-has-not-been-published-and-never-will@0.1.0`",
+        ))
+        .failure();
+}
+
 #[test]
 fn diff_with_crate_not_published() {
     let tempdir = tempfile::tempdir().unwrap();
@@ -760,6 +900,44 @@ fn diff_public_items_without_git_root() {
     cmd.assert().failure();
 }
 
+#[test]
+fn diff_public_items_with_elided_common_tokens() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("diff");
+    cmd.arg("--elide-common-tokens");
+    cmd.arg("v0.1.0..v0.2.0");
+    cmd.assert()
+        .stdout_or_update(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0_elided_common_tokens.txt",
+        )
+        .success();
+}
+
+#[test]
+fn diff_public_items_with_bump_from() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("diff");
+    cmd.arg("--bump-from=0.1.0");
+    cmd.arg("v0.1.0..v0.2.0");
+    cmd.assert()
+        .stdout(contains(
+            "\n\nDetected MAJOR changes; recommend bumping 0.1.0 → 0.2.0\n",
+        ))
+        .success();
+}
+
+#[test]
+fn diff_public_items_with_invalid_bump_from() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("diff");
+    cmd.arg("--bump-from=latest");
+    cmd.assert()
+        .stderr(contains(
+            "invalid value 'latest' for '--bump-from <VERSION>'",
+        ))
+        .failure();
+}
+
 #[test]
 fn diff_public_items_with_color() {
     let mut cmd = TestCmd::new().with_test_repo();
@@ -789,6 +967,31 @@ fn list_public_items_with_color_impl(color_arg: &str) {
         .success();
 }
 
+#[test]
+fn list_public_items_as_json_lines() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--format=jsonl");
+    let assert = cmd.assert().success();
+
+    // Each line must be a self-contained item with exactly the documented
+    // fields, and render the same way as without `--format=jsonl`
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout);
+    let rendered: String = stdout
+        .lines()
+        .map(|line| {
+            let serde_json::Value::Object(item) = serde_json::from_str(line).unwrap() else {
+                panic!("not an object: {line}");
+            };
+            let mut keys = item.keys().collect::<Vec<_>>();
+            keys.sort();
+            assert_eq!(keys, vec!["kind", "path", "text", "tokens"]);
+            format!("{}\n", item["text"].as_str().unwrap())
+        })
+        .collect();
+
+    expect_test::expect_file!["./expected-output/example_api-v0.3.0.txt"].assert_eq(&rendered);
+}
+
 #[test]
 fn diff_public_items_from_files_with_subcommand() {
     // Create independent build dirs so all tests can run in parallel
@@ -922,6 +1125,37 @@ fn diff_published_with_all_features() {
         .success();
 }
 
+#[test]
+fn show_published_crate() {
+    let mut cmd = TestCmd::new().with_separate_target_dir();
+    cmd.arg("show");
+    cmd.arg("example_api@0.3.0");
+    cmd.assert()
+        .stdout_or_update("./expected-output/example_api-v0.3.0.txt")
+        .success();
+}
+
+#[test]
+fn show_latest_published_crate() {
+    let mut cmd = TestCmd::new().with_separate_target_dir();
+    cmd.arg("show");
+    cmd.arg("example_api");
+    cmd.assert()
+        .stdout_or_update("./expected-output/example_api-v0.3.0.txt")
+        .stderr(contains("Resolved `example_api` to `example_api@0.3.0`"))
+        .success();
+}
+
+#[test]
+fn show_with_invalid_spec() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("show");
+    cmd.arg("example_api@");
+    cmd.assert()
+        .stderr("Error: Expected `crate` or `crate@version` but got `example_api@`\n")
+        .failure();
+}
+
 #[test]
 fn list_public_items_from_json_file() {
     // Create independent build dir so all tests can run in parallel
@@ -943,11 +1177,46 @@ fn verbose() {
     cmd.arg("../test-apis/lint_error/Cargo.toml");
     cmd.arg("--verbose");
     cmd.assert()
-        .stdout(contains("Processing \""))
-        .stdout(contains("rustdoc JSON missing referenced item"))
+        .stderr(contains("Processing \""))
+        .stderr(contains("rustdoc JSON missing referenced item"))
+        .success();
+}
+
+#[test]
+fn verbose_reports_phase_timings() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--verbose");
+    cmd.arg("diff");
+    cmd.arg("v0.2.0..v0.3.0");
+    cmd.assert()
+        .stderr(contains("Building rustdoc JSON took "))
+        .stderr(contains("Parsing and rendering rustdoc JSON took "))
+        .stderr(contains("Diffing public APIs took "))
         .success();
 }
 
+#[test]
+fn quiet_only_prints_result() {
+    let mut cmd = TestCmd::new().with_test_repo();
+    cmd.arg("--quiet");
+    cmd.arg("diff");
+    cmd.arg("v0.2.0..v0.3.0");
+    cmd.assert()
+        .stdout_or_update("./expected-output/example_api_diff_v0.2.0_to_v0.3.0.txt")
+        .stderr("")
+        .success();
+}
+
+#[test]
+fn verbose_and_quiet_conflict() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--verbose");
+    cmd.arg("--quiet");
+    cmd.assert()
+        .stderr(contains("cannot be used with"))
+        .failure();
+}
+
 #[test]
 fn short_help() {
     let mut cmd = TestCmd::new().with_separate_target_dir();
@@ -0,0 +1,53 @@
+//! Compares diffing against a baseline over and over again, like a CI bot that
+//! diffs every PR against `main` does, with and without a cached
+//! [`ApiIndex`]. Run with `cargo run --release --example bench_api_index`.
+
+use std::error::Error;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use public_api::diff::{ApiIndex, PublicApiDiff};
+
+const ITERATIONS: u32 = 100;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let old_json = rustdoc_json::Builder::default()
+        .toolchain("nightly")
+        .manifest_path("test-apis/example_api-v0.1.0/Cargo.toml")
+        .build()?;
+    let new_json = rustdoc_json::Builder::default()
+        .toolchain("nightly")
+        .manifest_path("test-apis/example_api-v0.2.0/Cargo.toml")
+        .build()?;
+
+    let rebuilt = time(|| {
+        let old = public_api::Builder::from_rustdoc_json(&old_json).build()?;
+        let new = public_api::Builder::from_rustdoc_json(&new_json).build()?;
+        black_box(PublicApiDiff::between(old, new));
+        Ok(())
+    })?;
+
+    let index = serde_json::to_string(&ApiIndex::new(
+        public_api::Builder::from_rustdoc_json(&old_json).build()?,
+    ))?;
+    let indexed = time(|| {
+        let old = serde_json::from_str::<ApiIndex>(&index)?;
+        let new = public_api::Builder::from_rustdoc_json(&new_json).build()?;
+        black_box(PublicApiDiff::between_index(&old, new)?);
+        Ok(())
+    })?;
+
+    println!("rebuilding the baseline: {rebuilt:?} per diff");
+    println!("cached ApiIndex:         {indexed:?} per diff");
+
+    Ok(())
+}
+
+/// Runs `diff` [`ITERATIONS`] times and returns the average time per run
+fn time(mut diff: impl FnMut() -> Result<(), Box<dyn Error>>) -> Result<Duration, Box<dyn Error>> {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        diff()?;
+    }
+    Ok(start.elapsed() / ITERATIONS)
+}
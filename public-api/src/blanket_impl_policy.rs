@@ -0,0 +1,17 @@
+/// Which Blanket Implementations such as `impl<T> Any for T` to include in the
+/// public API. Blanket impls defined in the crate itself, e.g. to implement an
+/// extension trait for all types, are usually deliberate public API, while
+/// those of dependencies and `core` are mostly noise.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More policies might be added in the future
+pub enum BlanketImplPolicy {
+    /// Include all blanket impls.
+    #[default]
+    All,
+
+    /// Only include blanket impls defined in the crate itself.
+    LocalOnly,
+
+    /// Omit all blanket impls.
+    None,
+}
@@ -0,0 +1,20 @@
+/// Where to render the bounds of generic type parameters. Rustdoc JSON keeps
+/// bounds where they were written, so `fn f<T: Clone>()` and `fn f<T>() where
+/// T: Clone` render differently even though they mean the same thing.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More styles might be added in the future
+pub enum BoundStyle {
+    /// Render bounds where they were written
+    #[default]
+    AsWritten,
+
+    /// Move bounds of generic type parameters into the parameter list, like
+    /// `fn f<T: Clone>()`. Where predicates that are not simply a bound on a
+    /// generic type parameter, such as `Vec<T>: Debug` or `for<'a> T:
+    /// Fn(&'a u8)`, stay in the where clause.
+    Inline,
+
+    /// Move bounds of generic type parameters into the where clause, like `fn
+    /// f<T>() where T: Clone`. Bounds of lifetime parameters stay inline.
+    WhereClause,
+}
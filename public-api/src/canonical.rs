@@ -0,0 +1,148 @@
+//! Normalizes rendered items so that items that only differ in ways that do
+//! not matter to users of the public API compare equal. See
+//! [`crate::canonical_hash()`].
+
+use std::collections::HashMap;
+
+use crate::public_item::PublicItem;
+use crate::tokens::{render_tokens, RenderStyle, Token};
+
+/// The canonical text of `item`. Lifetimes are renamed both before and after
+/// bounds are sorted, so that the sort order does not depend on the original
+/// lifetime names, and the final names do not depend on the original bound
+/// order. Last, whitespace is minimized.
+pub(crate) fn canonical_string(item: &PublicItem) -> String {
+    let tokens = rename_lifetimes(item.tokens.clone());
+    let tokens = rename_lifetimes(sort_bounds(&tokens));
+    render_tokens(&tokens, RenderStyle::Compact)
+}
+
+/// Renames lifetimes to `'0`, `'1`, etc. in order of first appearance.
+/// `'static` and `'_` are kept since they mean something specific.
+fn rename_lifetimes(mut tokens: Vec<Token>) -> Vec<Token> {
+    let mut new_names = HashMap::new();
+    for token in &mut tokens {
+        if let Token::Lifetime(name) = token {
+            if name != "'static" && name != "'_" {
+                let next_name = format!("'{}", new_names.len());
+                *name = new_names.entry(name.clone()).or_insert(next_name).clone();
+            }
+        }
+    }
+    tokens
+}
+
+/// Sorts the bounds of each list of bounds, such as `Send + Clone` in `T:
+/// Send + Clone`, by their compact text. Bracketed groups are handled
+/// recursively, so e.g. `Box<dyn Send + Debug>` is sorted too.
+fn sort_bounds(tokens: &[Token]) -> Vec<Token> {
+    // Split the tokens into units, where a unit is either a single token or a
+    // whole bracketed group. That way a `+` or `,` inside e.g. `<...>` does not
+    // affect the level we are at
+    let mut units = vec![];
+    let mut index = 0;
+    while index < tokens.len() {
+        if let Some(close) = matching_close(tokens, index) {
+            let mut group = vec![tokens[index].clone()];
+            group.extend(sort_bounds(&tokens[index + 1..close]));
+            group.extend(tokens.get(close).cloned());
+            units.push(group);
+            index = close + 1;
+        } else {
+            units.push(vec![tokens[index].clone()]);
+            index += 1;
+        }
+    }
+
+    let mut output = vec![];
+    let mut run = vec![];
+    for unit in units {
+        if matches!(unit.as_slice(), [token] if ends_bound_list(token)) {
+            output.extend(sort_bound_list(std::mem::take(&mut run)));
+            output.extend(unit);
+        } else {
+            run.push(unit);
+        }
+    }
+    output.extend(sort_bound_list(run));
+    output
+}
+
+/// If the token at `open` opens a bracketed group, the index of the token that
+/// closes it, or `tokens.len()` if it is never closed.
+fn matching_close(tokens: &[Token], open: usize) -> Option<usize> {
+    let is_open = |token: &Token| matches!(token.text(), "<" | "(" | "[");
+    let is_close = |token: &Token| matches!(token.text(), ">" | ")" | "]");
+
+    if !is_open(&tokens[open]) {
+        return None;
+    }
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        if is_open(token) {
+            depth += 1;
+        } else if is_close(token) {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    Some(tokens.len())
+}
+
+/// Whether `token` separates a list of bounds from what comes before or after
+/// it, e.g. the `:` in `T: Send + Clone` or the `dyn` in `dyn Send + Clone`.
+fn ends_bound_list(token: &Token) -> bool {
+    match token {
+        Token::Symbol(text) => matches!(text.as_str(), "," | ":" | "=" | ";" | "->"),
+        Token::Keyword(text) => matches!(text.as_str(), "where" | "impl" | "dyn"),
+        _ => false,
+    }
+}
+
+/// Sorts the `+`-separated parts of `run`, if there are several. Whitespace
+/// around the list is kept where it is.
+fn sort_bound_list(mut run: Vec<Vec<Token>>) -> Vec<Token> {
+    let is_whitespace = |unit: &Vec<Token>| unit.as_slice() == [Token::Whitespace];
+    let is_plus =
+        |unit: &Vec<Token>| matches!(unit.as_slice(), [Token::Symbol(text)] if text == "+");
+
+    if !run.iter().any(is_plus) {
+        return run.concat();
+    }
+
+    let leading = run.iter().take_while(|unit| is_whitespace(unit)).count();
+    let trailing = run
+        .iter()
+        .rev()
+        .take_while(|unit| is_whitespace(unit))
+        .count();
+    let suffix = run.split_off(run.len() - trailing);
+    let bounds = run.split_off(leading);
+
+    let mut parts = bounds
+        .split(is_plus)
+        .map(|part| {
+            let start = part.iter().take_while(|unit| is_whitespace(unit)).count();
+            let end = part.len()
+                - part
+                    .iter()
+                    .rev()
+                    .take_while(|unit| is_whitespace(unit))
+                    .count();
+            part[start..end.max(start)].concat()
+        })
+        .collect::<Vec<_>>();
+    parts.sort_by_cached_key(|part| render_tokens(part, RenderStyle::Compact));
+
+    let mut output = run.concat();
+    for (index, part) in parts.into_iter().enumerate() {
+        if index > 0 {
+            output.extend([Token::Whitespace, Token::symbol("+"), Token::Whitespace]);
+        }
+        output.extend(part);
+    }
+    output.extend(suffix.concat());
+    output
+}
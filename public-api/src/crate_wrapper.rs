@@ -30,6 +30,15 @@ impl<'c> CrateWrapper<'c> {
         })
     }
 
+    /// The full path of the item with the given [`Id`], e.g. `["core", "fmt",
+    /// "Debug"]`. Also works for items of other crates.
+    pub fn path_of(&self, id: &Id) -> Option<&'c [String]> {
+        self.crate_
+            .paths
+            .get(id)
+            .map(|summary| summary.path.as_slice())
+    }
+
     pub fn missing_item_ids(&self) -> Vec<String> {
         self.missing_ids.iter().map(|m| m.0.clone()).collect()
     }
@@ -4,14 +4,21 @@
 //! additional helpers for that.
 
 use crate::{
-    public_item::{PublicItem, PublicItemPath},
-    PublicApi,
+    item_processor::{formatted_sorting_prefix, MODULE_SORTING_PREFIX},
+    public_item::{split_sorting_prefix, PublicItem, PublicItemPath},
+    tokens::{tokens_to_string, Token},
+    Error, ItemKind, PublicApi, Result,
 };
 use hashbag::HashBag;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 type ItemsWithPath = HashMap<PublicItemPath, Vec<PublicItem>>;
 
+/// What must be the same for two items to be considered the same item, moved
+/// to a different module. See [`moved_key`].
+type MovedKey = (ItemKind, PublicItemPath, String);
+
 /// An item has changed in the public API. Two [`PublicItem`]s are considered
 /// the same if their `path` is the same.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -24,6 +31,79 @@ pub struct ChangedPublicItem {
 }
 
 impl ChangedPublicItem {
+    /// What kind of change this is. Allows e.g. reviewers to treat changes
+    /// that only reorder generic parameters differently from other changes.
+    #[must_use]
+    pub fn category(&self) -> ChangeCategory {
+        if self.old.tokens == self.new.tokens && self.old.doc_hidden != self.new.doc_hidden {
+            if self.new.doc_hidden {
+                ChangeCategory::BecameDocHidden
+            } else {
+                ChangeCategory::NoLongerDocHidden
+            }
+        } else if without_non_breaking_attributes(&self.old.tokens)
+            == without_non_breaking_attributes(&self.new.tokens)
+        {
+            ChangeCategory::MustUseOrDeprecationChanged
+        } else if generic_params_reordered(&self.old.tokens, &self.new.tokens) {
+            ChangeCategory::GenericParamsReordered
+        } else {
+            ChangeCategory::Other
+        }
+    }
+
+    /// Whether the change breaks downstream crates. Only changes to
+    /// `#[doc(hidden)]`, `#[must_use]` and `#[deprecated]` do not.
+    fn is_breaking(&self) -> bool {
+        !matches!(
+            self.category(),
+            ChangeCategory::BecameDocHidden
+                | ChangeCategory::NoLongerDocHidden
+                | ChangeCategory::MustUseOrDeprecationChanged
+        )
+    }
+
+    /// For a changed function or method, how its parameters and return type
+    /// changed. Parameters are matched up by name, so a renamed parameter
+    /// shows up as one removed and one added parameter. Returns `None` for
+    /// other kinds of items.
+    #[must_use]
+    pub fn param_changes(&self) -> Option<ParamDiff> {
+        let is_fn = |item: &PublicItem| matches!(item.kind, ItemKind::Function | ItemKind::Method);
+        if !is_fn(&self.old) || !is_fn(&self.new) {
+            return None;
+        }
+        let (old_params, old_return_type) = split_fn_signature(&self.old.tokens)?;
+        let (new_params, new_return_type) = split_fn_signature(&self.new.tokens)?;
+
+        let mut removed = old_params;
+        let mut added = vec![];
+        let mut retyped = vec![];
+        for new in new_params {
+            match removed.iter().position(|old| old.name == new.name) {
+                Some(index) => {
+                    let old = removed.remove(index);
+                    if old.type_ != new.type_ {
+                        retyped.push(RetypedParam {
+                            name: new.name,
+                            old_type: old.type_,
+                            new_type: new.type_,
+                        });
+                    }
+                }
+                None => added.push(new),
+            }
+        }
+
+        Some(ParamDiff {
+            added,
+            removed,
+            retyped,
+            old_return_type,
+            new_return_type,
+        })
+    }
+
     /// See [`PublicItem::grouping_cmp`]
     #[must_use]
     pub fn grouping_cmp(&self, other: &Self) -> std::cmp::Ordering {
@@ -34,6 +114,211 @@ impl ChangedPublicItem {
     }
 }
 
+/// How the signature of a function or method changed. See
+/// [`ChangedPublicItem::param_changes()`]. Types are rendered as text, like in
+/// [`PublicItem`]s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamDiff {
+    /// Parameters that only the new signature has.
+    pub added: Vec<Param>,
+
+    /// Parameters that only the old signature has.
+    pub removed: Vec<Param>,
+
+    /// Parameters that both signatures have, but with different types.
+    pub retyped: Vec<RetypedParam>,
+
+    /// The return type of the old signature, or `()` if it has none.
+    pub old_return_type: String,
+
+    /// The return type of the new signature, or `()` if it has none.
+    pub new_return_type: String,
+}
+
+impl ParamDiff {
+    /// Whether the return type changed.
+    #[must_use]
+    pub fn return_type_changed(&self) -> bool {
+        self.old_return_type != self.new_return_type
+    }
+}
+
+/// A parameter of a function or method. See [`ParamDiff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    /// The name of the parameter, e.g. `x` in `x: usize`. `self` for
+    /// receivers, and `_` for parameters without a name.
+    pub name: String,
+
+    /// The type of the parameter, e.g. `usize` in `x: usize`. For receivers
+    /// like `&self`, this is the type the receiver is short for, e.g. `&Self`.
+    pub type_: String,
+}
+
+/// A parameter whose type changed. See [`ParamDiff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetypedParam {
+    /// The name of the parameter.
+    pub name: String,
+
+    /// The type of the parameter in the old signature.
+    pub old_type: String,
+
+    /// The type of the parameter in the new signature.
+    pub new_type: String,
+}
+
+/// What kind of change a [`ChangedPublicItem`] is. See
+/// [`ChangedPublicItem::category()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More categories might be distinguished in the future
+pub enum ChangeCategory {
+    /// Only the order of the generic parameters of a function changed, e.g.
+    /// `fn f<T, U>()` became `fn f<U, T>()`. Still a breaking change for
+    /// callers that specify generic arguments explicitly with turbofish, but
+    /// often a harmless refactoring.
+    GenericParamsReordered,
+
+    /// The item is unchanged, except that it has been marked `#[doc(hidden)]`.
+    /// Crates sometimes hide items as a soft deprecation before removing them
+    /// in a later release, so this category lets you tell such deliberate
+    /// pre-removal steps apart from other changes. Only detected if the rustdoc
+    /// JSON was built with `--document-hidden-items`, see
+    /// [`PublicItem::is_doc_hidden()`].
+    BecameDocHidden,
+
+    /// The item is unchanged, except that it is no longer `#[doc(hidden)]`.
+    /// See [`Self::BecameDocHidden`].
+    NoLongerDocHidden,
+
+    /// The item is unchanged, except that a `#[must_use]` or `#[deprecated]`
+    /// attribute was added, removed or changed. At most this makes downstream
+    /// crates get new warnings.
+    MustUseOrDeprecationChanged,
+
+    /// Any other change.
+    Other,
+}
+
+/// How a change to a public API affects the version number of a crate, in
+/// [semver](https://semver.org) terminology. Ordered from least to most
+/// severe.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemverImpact {
+    /// The public API is unchanged.
+    Patch,
+
+    /// The public API has only been extended in a backwards compatible way.
+    Minor,
+
+    /// The public API has changed in a way that can break downstream crates.
+    Major,
+}
+
+/// A change that breaks downstream crates, together with why. See
+/// [`breaking_changes()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BreakingChange {
+    /// The affected item. For [`BreakingChangeCategory::Added`] this is the
+    /// added item, otherwise it is the item as it looked before the change,
+    /// i.e. the item that downstream crates depend on.
+    pub item: PublicItem,
+
+    /// What kind of change it is.
+    pub category: BreakingChangeCategory,
+
+    /// A short explanation for humans, e.g. `public function removed`.
+    pub reason: String,
+}
+
+/// What kind of change a [`BreakingChange`] is. Corresponds to the field of
+/// [`PublicApiDiff`] that the change comes from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More categories might be distinguished in the future
+pub enum BreakingChangeCategory {
+    /// The item was removed, see [`PublicApiDiff::removed`].
+    Removed,
+
+    /// The item was changed, e.g. the signature of a function, see
+    /// [`PublicApiDiff::changed`].
+    Changed,
+
+    /// The item moved to a different module, see [`PublicApiDiff::moved`].
+    Moved,
+
+    /// The item was added in a way that breaks downstream crates, see
+    /// [`PublicApiDiff::breaking_additions()`].
+    Added,
+}
+
+/// The labels that [`render_removed()`], [`render_added()`] and
+/// [`render_changed()`] put in front of items, e.g. `REMOVED` in `REMOVED: pub
+/// fn krate::f()`. Set the fields to e.g. translate the labels or to match the
+/// style of a changelog.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLabels {
+    /// In front of removed items. `REMOVED` by default.
+    pub removed: String,
+
+    /// In front of added items. `ADDED` by default.
+    pub added: String,
+
+    /// In front of the old version of changed items. `CHANGED FROM` by default.
+    pub changed_from: String,
+
+    /// In front of the new version of changed items. `CHANGED TO` by default.
+    pub changed_to: String,
+}
+
+impl Default for DiffLabels {
+    fn default() -> Self {
+        Self {
+            removed: String::from("REMOVED"),
+            added: String::from("ADDED"),
+            changed_from: String::from("CHANGED FROM"),
+            changed_to: String::from("CHANGED TO"),
+        }
+    }
+}
+
+/// How severe a GitHub Actions annotation created by
+/// [`to_github_annotations()`] is. Decides how GitHub shows it, and errors
+/// also make GitHub show the workflow step as failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnnotationLevel {
+    /// A `::notice` annotation.
+    Notice,
+
+    /// A `::warning` annotation.
+    Warning,
+
+    /// An `::error` annotation.
+    Error,
+}
+
+/// What [`AnnotationLevel`] [`to_github_annotations()`] uses for what kind of
+/// change. Changes with a level of `None` get no annotation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GithubAnnotationLevels {
+    /// For [breaking changes](breaking_changes()). [`AnnotationLevel::Error`]
+    /// by default.
+    pub breaking: Option<AnnotationLevel>,
+
+    /// For [additions that are not
+    /// breaking](PublicApiDiff::non_breaking_additions()).
+    /// [`AnnotationLevel::Warning`] by default.
+    pub non_breaking: Option<AnnotationLevel>,
+}
+
+impl Default for GithubAnnotationLevels {
+    fn default() -> Self {
+        Self {
+            breaking: Some(AnnotationLevel::Error),
+            non_breaking: Some(AnnotationLevel::Warning),
+        }
+    }
+}
+
 /// The return value of [`Self::between`]. To quickly get a sense of what it
 /// contains, you can pretty-print it:
 /// ```txt
@@ -54,9 +339,60 @@ pub struct PublicApiDiff {
     /// Items that have been added to public API. A MINOR change, in semver
     /// terminology. Sorted.
     pub added: Vec<PublicItem>,
+
+    /// Items that have moved to a different module, as `(old, new)` pairs. A
+    /// MAJOR change, in semver terminology. Always empty unless
+    /// [`Self::detect_moved_items()`] has been called. Sorted.
+    pub moved: Vec<(PublicItem, PublicItem)>,
 }
 
 impl PublicApiDiff {
+    /// Like [`Self::between()`], but fails with [`Error::CrateNameMismatch`]
+    /// if `old` and `new` are the public APIs of crates with different names.
+    /// Diffing unrelated crates is rarely intended, and the resulting diff is
+    /// just noise.
+    ///
+    /// # Errors
+    ///
+    /// If the crate names of `old` and `new` are both known and differ.
+    pub fn try_between(old: PublicApi, new: PublicApi) -> Result<Self> {
+        if let (Some(old_name), Some(new_name)) = (old.crate_name(), new.crate_name()) {
+            if old_name != new_name {
+                return Err(Error::CrateNameMismatch {
+                    old: old_name.to_owned(),
+                    new: new_name.to_owned(),
+                });
+            }
+        }
+
+        Ok(Self::between(old, new))
+    }
+
+    /// Like [`Self::between()`], but with the old public API taken from a
+    /// precomputed [`ApiIndex`].
+    ///
+    /// # Errors
+    ///
+    /// [`Error::StaleApiIndex`] if `index` was created by a different version
+    /// of this library.
+    pub fn between_index(index: &ApiIndex, new: PublicApi) -> Result<Self> {
+        let current_version = env!("CARGO_PKG_VERSION");
+        if index.format_version != current_version {
+            return Err(Error::StaleApiIndex {
+                index_version: index.format_version.clone(),
+                current_version: current_version.to_owned(),
+            });
+        }
+
+        let old = PublicApi {
+            items: index.items.clone(),
+            missing_item_ids: vec![],
+            target: None,
+            crate_name: None,
+        };
+        Ok(Self::between(old, new))
+    }
+
     /// Allows you to diff the public API between two arbitrary versions of a
     /// library, e.g. different releases. The input parameters `old` and `new`
     /// is the output of two different invocations of
@@ -65,9 +401,19 @@ impl PublicApiDiff {
     pub fn between(old: PublicApi, new: PublicApi) -> Self {
         // We must use a HashBag, because with a HashSet we would lose public
         // items that happen to have the same representation due to limitations
-        // or bugs
-        let old = old.into_items().collect::<HashBag<_>>();
-        let new = new.into_items().collect::<HashBag<_>>();
+        // or bugs.
+        //
+        // Items are compared by their rendering, which does not include
+        // `#[doc(hidden)]`. Pair each item with its hidden-ness so that an item
+        // that only became (un)hidden ends up as a changed item.
+        let old = old
+            .into_items()
+            .map(|item| (item.doc_hidden, item))
+            .collect::<HashBag<_>>();
+        let new = new
+            .into_items()
+            .map(|item| (item.doc_hidden, item))
+            .collect::<HashBag<_>>();
 
         // First figure out what items have been removed and what have been
         // added. Later we will match added and removed items with the same path
@@ -117,118 +463,1102 @@ impl PublicApiDiff {
             removed,
             changed,
             added,
+            moved: vec![],
         }
     }
 
+    /// Like [`Self::between()`], but first renames paths in `old` according to
+    /// `renames`, so that an intentional rename, e.g. of a module, does not
+    /// show up as all of its items being removed and added. Each rename is a
+    /// `(from, to)` pair such as `(vec!["krate", "old_mod"], vec!["krate",
+    /// "new_mod"])`. Paths that start with `from` then start with `to`
+    /// instead, both the paths of the items themselves and paths within them,
+    /// e.g. in the types of function parameters. Only the first matching
+    /// rename is applied to a path.
+    #[must_use]
+    pub fn between_with_renames(
+        mut old: PublicApi,
+        new: PublicApi,
+        renames: &[(Vec<String>, Vec<String>)],
+    ) -> Self {
+        old.items = std::mem::take(&mut old.items)
+            .into_iter()
+            .map(|item| renamed_item(item, renames))
+            .collect();
+        Self::between(old, new)
+    }
+
+    /// Pairs items in [`Self::removed`] with items in [`Self::added`] that
+    /// render the same except for the module they are in, and puts the pairs
+    /// in [`Self::moved`]. For example, a `pub struct krate::old::Foo` that was
+    /// removed and a `pub struct krate::new::Foo` that was added become one
+    /// moved item. Unlike renames, the name of the item itself must not change.
+    ///
+    /// Opt-in, since unrelated items in different modules can happen to have
+    /// the same name and rendering.
+    #[must_use]
+    pub fn detect_moved_items(mut self) -> Self {
+        let mut removed_by_key: HashMap<MovedKey, Vec<PublicItem>> = HashMap::new();
+        for old in std::mem::take(&mut self.removed) {
+            removed_by_key.entry(moved_key(&old)).or_default().push(old);
+        }
+
+        for new in std::mem::take(&mut self.added) {
+            let old = removed_by_key
+                .get_mut(&moved_key(&new))
+                .and_then(|candidates| {
+                    let index = candidates
+                        .iter()
+                        .position(|old| module_path(old) != module_path(&new))?;
+                    Some(candidates.remove(index))
+                });
+            match old {
+                Some(old) => self.moved.push((old, new)),
+                None => self.added.push(new),
+            }
+        }
+        self.removed = removed_by_key.into_values().flatten().collect();
+
+        self.removed.sort_by(PublicItem::grouping_cmp);
+        self.added.sort_by(PublicItem::grouping_cmp);
+        self.moved.sort_by(moved_grouping_cmp);
+
+        self
+    }
+
+    /// Combines two diffs into one, e.g. when diffing a workspace crate by
+    /// crate. The items of both diffs are kept and sorted the same way as
+    /// [`Self::between()`] sorts them. For diffs of disjoint crates, the
+    /// result is the same as diffing the combined public APIs.
+    #[must_use]
+    pub fn merge(mut self, other: Self) -> Self {
+        self.removed.extend(other.removed);
+        self.changed.extend(other.changed);
+        self.added.extend(other.added);
+        self.moved.extend(other.moved);
+
+        self.removed.sort_by(PublicItem::grouping_cmp);
+        self.changed.sort_by(ChangedPublicItem::grouping_cmp);
+        self.added.sort_by(PublicItem::grouping_cmp);
+        self.moved.sort_by(moved_grouping_cmp);
+
+        self
+    }
+
     /// Check whether the diff is empty
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.removed.is_empty() && self.changed.is_empty() && self.added.is_empty()
+        self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.added.is_empty()
+            && self.moved.is_empty()
+    }
+
+    /// Items in [`Self::added`] that break downstream crates even though they
+    /// are additions. For example, a new field in a struct that other crates
+    /// can create with a struct expression, a new variant in an enum that
+    /// other crates can match on exhaustively, or a new trait method without a
+    /// default body that other crates must implement. Additions to
+    /// `#[non_exhaustive]` types are not breaking and are thus not included.
+    /// Neither are the fields, variants and trait items of types and traits
+    /// that are themselves added, since no other crate can use them yet.
+    pub fn breaking_additions(&self) -> impl Iterator<Item = &PublicItem> {
+        let is_breaking_addition = self.is_breaking_addition();
+        self.added
+            .iter()
+            .filter(move |item| is_breaking_addition(item))
+    }
+
+    /// Items in [`Self::added`] that do not break downstream crates, i.e. all
+    /// additions except [`Self::breaking_additions()`]. For example, a new
+    /// trait method with a default body is included here, since existing
+    /// impls of the trait still compile, while a new required trait method is
+    /// not.
+    pub fn non_breaking_additions(&self) -> impl Iterator<Item = &PublicItem> {
+        let is_breaking_addition = self.is_breaking_addition();
+        self.added
+            .iter()
+            .filter(move |item| !is_breaking_addition(item))
+    }
+
+    /// Tells if an item in [`Self::added`] is one of the
+    /// [`Self::breaking_additions()`]. The parent of such an item, e.g. the
+    /// struct of a field, must not have been added itself.
+    fn is_breaking_addition(&self) -> impl Fn(&PublicItem) -> bool + '_ {
+        let added_paths: HashSet<&[String]> = self
+            .added
+            .iter()
+            .map(|item| item.sortable_path.as_slice())
+            .collect();
+        move |item| {
+            let parent_path = &item.sortable_path[..item.sortable_path.len().saturating_sub(1)];
+            item.in_exhaustive_type && !added_paths.contains(parent_path)
+        }
+    }
+
+    /// The [`SemverImpact`] of the diff as a whole. Removed, changed and moved
+    /// items are always considered [`SemverImpact::Major`], as are
+    /// [breaking additions](Self::breaking_additions). Items that only became
+    /// or stopped being `#[doc(hidden)]` are ignored, since toggling
+    /// `#[doc(hidden)]` is a soft deprecation and does not break anyone. The
+    /// same goes for items that only got new `#[must_use]` or `#[deprecated]`
+    /// attributes, see [`ChangeCategory::MustUseOrDeprecationChanged`].
+    #[must_use]
+    pub fn semver_impact(&self) -> SemverImpact {
+        if !self.removed.is_empty()
+            || self.changed.iter().any(ChangedPublicItem::is_breaking)
+            || !self.moved.is_empty()
+            || self.breaking_additions().next().is_some()
+        {
+            SemverImpact::Major
+        } else if !self.added.is_empty() {
+            SemverImpact::Minor
+        } else {
+            SemverImpact::Patch
+        }
+    }
+
+    /// Whether the diff breaks downstream crates, i.e. whether its
+    /// [`Self::semver_impact()`] is [`SemverImpact::Major`]. Additions are
+    /// ignored unless they are [breaking](Self::breaking_additions).
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.semver_impact() == SemverImpact::Major
+    }
+
+    /// Groups [`Self::removed`] by [`ItemKind`]. Convenient if you want to
+    /// summarize the diff as e.g. "2 structs removed".
+    #[must_use]
+    pub fn removed_by_kind(&self) -> BTreeMap<ItemKind, Vec<&PublicItem>> {
+        group_by_kind(&self.removed)
+    }
+
+    /// Groups [`Self::added`] by [`ItemKind`]. Convenient if you want to
+    /// summarize the diff as e.g. "1 function added".
+    #[must_use]
+    pub fn added_by_kind(&self) -> BTreeMap<ItemKind, Vec<&PublicItem>> {
+        group_by_kind(&self.added)
     }
 }
 
-/// Converts a set (read: bag) of public items into a hash map that maps a given
-/// path to a vec of public items with that path.
-fn bag_to_path_map<'a>(difference: impl Iterator<Item = (&'a PublicItem, usize)>) -> ItemsWithPath {
-    let mut map: ItemsWithPath = HashMap::new();
-    for (item, occurrences) in difference {
-        let items = map.entry(item.sortable_path.clone()).or_default();
-        for _ in 0..occurrences {
-            items.push(item.clone());
+/// A precomputed public API to diff against with
+/// [`PublicApiDiff::between_index`]. Useful when the same baseline, e.g. the
+/// `main` branch of a repo, is diffed against over and over again. Create the
+/// index once, serialize it with [`serde`] to e.g. a cache file, and then
+/// deserialize it each time you need it, instead of building rustdoc JSON and
+/// a [`PublicApi`] for the baseline every time.
+///
+/// The index is tagged with the version of this library that created it, since
+/// items can be rendered differently between versions.
+/// [`PublicApiDiff::between_index`] rejects indexes created by other versions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiIndex {
+    /// The version of this library that created the index
+    format_version: String,
+
+    /// The items of the baseline public API, sorted with
+    /// [`PublicItem::grouping_cmp`]
+    items: Vec<PublicItem>,
+}
+
+impl ApiIndex {
+    /// Creates an index of `public_api`, to later be passed to
+    /// [`PublicApiDiff::between_index`].
+    #[must_use]
+    pub fn new(public_api: PublicApi) -> Self {
+        let mut items = public_api.items;
+        items.sort_by(PublicItem::grouping_cmp);
+        Self {
+            format_version: env!("CARGO_PKG_VERSION").to_owned(),
+            items,
         }
     }
-    map
+
+    /// Returns an iterator over all items in the index
+    pub fn items(&self) -> impl Iterator<Item = &'_ PublicItem> {
+        self.items.iter()
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::tokens::Token;
+/// The return value of [`diff_targets`]. Describes how the public API of a
+/// crate differs between two target triples, e.g. because of `#[cfg(...)]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetsDiff {
+    /// The target of the first public API, see [`PublicApi::target()`].
+    pub first_target: Option<String>,
 
-    use super::*;
+    /// The target of the second public API, see [`PublicApi::target()`].
+    pub second_target: Option<String>,
 
-    #[test]
-    fn single_and_only_item_removed() {
-        let old = api([item_with_path("foo")]);
-        let new = api([]);
+    /// Items that only exist in the public API of the first target. Sorted.
+    pub only_in_first: Vec<PublicItem>,
 
-        let actual = PublicApiDiff::between(old, new);
-        let expected = PublicApiDiff {
-            removed: vec![item_with_path("foo")],
-            changed: vec![],
-            added: vec![],
-        };
-        assert_eq!(actual, expected);
-        assert!(!actual.is_empty());
+    /// Items that exist for both targets but look different. Sorted.
+    pub differing: Vec<ChangedPublicItem>,
+
+    /// Items that only exist in the public API of the second target. Sorted.
+    pub only_in_second: Vec<PublicItem>,
+}
+
+impl TargetsDiff {
+    /// Check whether the public API is identical for both targets
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.only_in_first.is_empty() && self.differing.is_empty() && self.only_in_second.is_empty()
     }
+}
 
-    #[test]
-    fn single_and_only_item_added() {
-        let old = api([]);
-        let new = api([item_with_path("foo")]);
+/// Compares the public API of the same crate built for two different targets.
+/// Build each [`PublicApi`] with [`crate::Builder::target()`] so that the
+/// result is labeled with what target each side came from.
+#[must_use]
+pub fn diff_targets(first: PublicApi, second: PublicApi) -> TargetsDiff {
+    let first_target = first.target.clone();
+    let second_target = second.target.clone();
+    let diff = PublicApiDiff::between(first, second);
+    TargetsDiff {
+        first_target,
+        second_target,
+        only_in_first: diff.removed,
+        differing: diff.changed,
+        only_in_second: diff.added,
+    }
+}
 
-        let actual = PublicApiDiff::between(old, new);
-        let expected = PublicApiDiff {
-            removed: vec![],
-            changed: vec![],
-            added: vec![item_with_path("foo")],
-        };
-        assert_eq!(actual, expected);
-        assert!(!actual.is_empty());
+/// Renders `diff` as plain text in the style of a unified diff, i.e. with
+/// removed items prefixed by `-` and added items prefixed by `+`. Changed and
+/// moved items become a `-old` line directly followed by a `+new` line.
+/// Removed items come first, then changed, moved and added items. Since all of
+/// these are sorted, the output is the same for the same diff.
+///
+/// Unlike the Markdown output of `cargo public-api`, this is meant to be read
+/// as-is or fed to `patch`-style tooling.
+#[must_use]
+pub fn to_unified(diff: &PublicApiDiff) -> String {
+    use std::fmt::Write;
+
+    let mut output = String::new();
+    for item in &diff.removed {
+        writeln!(output, "-{item}").unwrap();
+    }
+    for ChangedPublicItem { old, new } in &diff.changed {
+        writeln!(output, "-{old}").unwrap();
+        writeln!(output, "+{new}").unwrap();
+    }
+    for (old, new) in &diff.moved {
+        writeln!(output, "-{old}").unwrap();
+        writeln!(output, "+{new}").unwrap();
+    }
+    for item in &diff.added {
+        writeln!(output, "+{item}").unwrap();
     }
+    output
+}
 
-    #[test]
-    fn middle_item_added() {
-        let old = api([item_with_path("1"), item_with_path("3")]);
-        let new = api([
-            item_with_path("1"),
-            item_with_path("2"),
-            item_with_path("3"),
-        ]);
+/// The rows of a two-column view of `diff`, with old items on the left and
+/// new items on the right. Removed items have `None` on the right, added items
+/// have `None` on the left, and changed and moved items have both. Rows are
+/// sorted the same way as the items of a [`PublicApi`], by the old item if
+/// there is one and by the new item otherwise, so that e.g. an added method
+/// ends up next to the changed methods of the same type.
+#[must_use]
+pub fn side_by_side(diff: &PublicApiDiff) -> Vec<(Option<String>, Option<String>)> {
+    let mut rows = vec![];
+    rows.extend(diff.removed.iter().map(|old| (Some(old), None)));
+    rows.extend(
+        diff.changed
+            .iter()
+            .map(|ChangedPublicItem { old, new }| (Some(old), Some(new))),
+    );
+    rows.extend(diff.moved.iter().map(|(old, new)| (Some(old), Some(new))));
+    rows.extend(diff.added.iter().map(|new| (None, Some(new))));
 
-        let actual = PublicApiDiff::between(old, new);
-        let expected = PublicApiDiff {
-            removed: vec![],
-            changed: vec![],
-            added: vec![item_with_path("2")],
-        };
-        assert_eq!(actual, expected);
-        assert!(!actual.is_empty());
+    // Stable, so rows that compare equal keep the order of the buckets above
+    fn key<'a>((old, new): &(Option<&'a PublicItem>, Option<&'a PublicItem>)) -> &'a PublicItem {
+        old.or(*new).unwrap()
     }
+    rows.sort_by(|a, b| key(a).grouping_cmp(key(b)));
 
-    #[test]
-    fn middle_item_removed() {
-        let old = api([
-            item_with_path("1"),
-            item_with_path("2"),
-            item_with_path("3"),
-        ]);
-        let new = api([item_with_path("1"), item_with_path("3")]);
+    rows.into_iter()
+        .map(|(old, new)| (old.map(ToString::to_string), new.map(ToString::to_string)))
+        .collect()
+}
 
-        let actual = PublicApiDiff::between(old, new);
-        let expected = PublicApiDiff {
-            removed: vec![item_with_path("2")],
-            changed: vec![],
-            added: vec![],
+/// One line per item in [`PublicApiDiff::removed`], labeled with
+/// [`DiffLabels::removed`], e.g. `REMOVED: pub fn krate::f()`.
+#[must_use]
+pub fn render_removed(diff: &PublicApiDiff, labels: &DiffLabels) -> Vec<String> {
+    diff.removed
+        .iter()
+        .map(|item| format!("{}: {item}", labels.removed))
+        .collect()
+}
+
+/// One line per item in [`PublicApiDiff::added`], labeled with
+/// [`DiffLabels::added`], e.g. `ADDED: pub fn krate::g()`.
+#[must_use]
+pub fn render_added(diff: &PublicApiDiff, labels: &DiffLabels) -> Vec<String> {
+    diff.added
+        .iter()
+        .map(|item| format!("{}: {item}", labels.added))
+        .collect()
+}
+
+/// Two lines per item in [`PublicApiDiff::changed`], the old version labeled
+/// with [`DiffLabels::changed_from`] followed by the new version labeled with
+/// [`DiffLabels::changed_to`].
+#[must_use]
+pub fn render_changed(diff: &PublicApiDiff, labels: &DiffLabels) -> Vec<String> {
+    diff.changed
+        .iter()
+        .flat_map(|ChangedPublicItem { old, new }| {
+            [
+                format!("{}: {old}", labels.changed_from),
+                format!("{}: {new}", labels.changed_to),
+            ]
+        })
+        .collect()
+}
+
+/// Lists every change in `diff` that breaks downstream crates, with a reason
+/// for each. Nonempty exactly when [`PublicApiDiff::is_breaking()`] is `true`.
+/// Useful to build error messages in CI or to write changelogs.
+///
+/// Removed items come first, then changed, moved and breaking added items, in
+/// the same order as in `diff`.
+#[must_use]
+pub fn breaking_changes(diff: &PublicApiDiff) -> Vec<BreakingChange> {
+    let change = |item: &PublicItem, category, reason| BreakingChange {
+        item: item.clone(),
+        category,
+        reason,
+    };
+
+    let mut changes = vec![];
+    for item in &diff.removed {
+        let reason = format!("public {} removed", item.kind.description());
+        changes.push(change(item, BreakingChangeCategory::Removed, reason));
+    }
+    for changed in &diff.changed {
+        let kind = changed.old.kind.description();
+        let reason = match changed.category() {
+            ChangeCategory::GenericParamsReordered => {
+                format!("generic parameters of {kind} reordered")
+            }
+            // Not breaking, see `PublicApiDiff::semver_impact()`
+            ChangeCategory::BecameDocHidden
+            | ChangeCategory::NoLongerDocHidden
+            | ChangeCategory::MustUseOrDeprecationChanged => continue,
+            ChangeCategory::Other
+                if matches!(changed.old.kind, ItemKind::Function | ItemKind::Method) =>
+            {
+                format!("signature of public {kind} changed")
+            }
+            ChangeCategory::Other => format!("public {kind} changed"),
         };
-        assert_eq!(actual, expected);
-        assert!(!actual.is_empty());
+        changes.push(change(
+            &changed.old,
+            BreakingChangeCategory::Changed,
+            reason,
+        ));
+    }
+    for (old, _) in &diff.moved {
+        let reason = format!("public {} moved to another module", old.kind.description());
+        changes.push(change(old, BreakingChangeCategory::Moved, reason));
+    }
+    for item in diff.breaking_additions() {
+        let reason = match item.kind {
+            ItemKind::StructField => String::from("field added to exhaustive type"),
+            ItemKind::Variant => String::from("variant added to exhaustive enum"),
+            kind => format!("required trait {} added", kind.description()),
+        };
+        changes.push(change(item, BreakingChangeCategory::Added, reason));
     }
+    changes
+}
 
-    #[test]
-    fn many_identical_items() {
-        let old = api([
-            item_with_path("1"),
-            item_with_path("2"),
-            item_with_path("2"),
-            item_with_path("3"),
-            item_with_path("3"),
-            item_with_path("3"),
-            fn_with_param_type(&["a", "b"], "i32"),
-            fn_with_param_type(&["a", "b"], "i32"),
-        ]);
-        let new = api([
-            item_with_path("1"),
-            item_with_path("2"),
+/// Renders `diff` as [GitHub Actions workflow
+/// commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions),
+/// one per line, so that GitHub shows the changes as annotations, e.g. inline
+/// in the files of a pull request. For example:
+/// ```txt
+/// ::error file=src/lib.rs,line=3,title=public function removed::pub fn krate::f()
+/// ```
+/// Breaking changes come first, in the order of [`breaking_changes()`],
+/// followed by additions that are not breaking. `levels` decides the
+/// [`AnnotationLevel`] of each. The `file` and `line` are only included if the
+/// public APIs were built with [`crate::Builder::include_spans()`]. Changed
+/// and moved items point at where the new item is defined, since that is what
+/// a pull request shows.
+#[must_use]
+pub fn to_github_annotations(diff: &PublicApiDiff, levels: &GithubAnnotationLevels) -> Vec<String> {
+    let mut annotations = vec![];
+    if let Some(level) = levels.breaking {
+        for change in breaking_changes(diff) {
+            let item = match change.category {
+                BreakingChangeCategory::Changed => diff
+                    .changed
+                    .iter()
+                    .find(|changed| changed.old == change.item)
+                    .map(|changed| &changed.new),
+                BreakingChangeCategory::Moved => diff
+                    .moved
+                    .iter()
+                    .find(|(old, _)| *old == change.item)
+                    .map(|(_, new)| new),
+                BreakingChangeCategory::Removed | BreakingChangeCategory::Added => None,
+            };
+            let item = item.unwrap_or(&change.item);
+            annotations.push(github_annotation(level, item, &change.reason));
+        }
+    }
+    if let Some(level) = levels.non_breaking {
+        for item in diff.non_breaking_additions() {
+            let title = format!("public {} added", item.kind.description());
+            annotations.push(github_annotation(level, item, &title));
+        }
+    }
+    annotations
+}
+
+/// The oldest version in `baselines` that `current` is backward compatible
+/// with, i.e. such that there are no [breaking changes](breaking_changes())
+/// from that version, nor from any newer version in `baselines`, to
+/// `current`. Returns `None` if `current` breaks even the newest baseline, or
+/// if `baselines` is empty.
+///
+/// `V` is typically a version type such as `semver::Version`, and the items
+/// of each baseline are typically from [`PublicApi::into_items()`]. The order
+/// of `baselines` does not matter.
+#[must_use]
+pub fn min_compatible_version<V: Ord + Clone>(
+    current: &[PublicItem],
+    baselines: &[(V, Vec<PublicItem>)],
+) -> Option<V> {
+    let api = |items: &[PublicItem]| PublicApi {
+        items: items.to_vec(),
+        missing_item_ids: vec![],
+        target: None,
+        crate_name: None,
+    };
+
+    let mut newest_first = baselines.iter().collect::<Vec<_>>();
+    newest_first.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    newest_first
+        .into_iter()
+        .take_while(|(_, items)| !PublicApiDiff::between(api(items), api(current)).is_breaking())
+        .last()
+        .map(|(version, _)| version.clone())
+}
+
+/// The version that a crate at `current_version` should be released as next,
+/// given the [`PublicApiDiff::semver_impact()`] of `diff`. Follows the rules
+/// of Cargo, which differ from plain semver before 1.0.0:
+///
+/// * `0.0.z`: every release is considered breaking, so `z` is always bumped.
+/// * `0.y.z`: breaking changes bump `y`, all other changes bump `z`.
+/// * `x.y.z`: breaking changes bump `x`, additions bump `y` and anything else
+///   bumps `z`.
+///
+/// Pre-release and build metadata such as `-alpha.1` are dropped. If
+/// `current_version` is not of the form `MAJOR.MINOR.PATCH`, it is returned as
+/// is.
+#[must_use]
+pub fn recommend_version(current_version: &str, diff: &PublicApiDiff) -> String {
+    let release = current_version.split(['-', '+']).next().unwrap_or_default();
+    let mut parts = release.split('.').map(|part| part.parse::<u64>().ok());
+    let (Some(Some(major)), Some(Some(minor)), Some(Some(patch)), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return current_version.to_owned();
+    };
+
+    let (major, minor, patch) = match (major, minor, diff.semver_impact()) {
+        (0, 0, _) => (0, 0, patch + 1),
+        (0, _, SemverImpact::Major) => (0, minor + 1, 0),
+        (0, _, SemverImpact::Minor | SemverImpact::Patch) => (0, minor, patch + 1),
+        (_, _, SemverImpact::Major) => (major + 1, 0, 0),
+        (_, _, SemverImpact::Minor) => (major, minor + 1, 0),
+        (_, _, SemverImpact::Patch) => (major, minor, patch + 1),
+    };
+    format!("{major}.{minor}.{patch}")
+}
+
+/// Explains how two diffs differ, e.g. to find out why a diff no longer matches
+/// a snapshot of it. Returns `None` if `a == b`. Otherwise, for each of
+/// [`PublicApiDiff::removed`], [`PublicApiDiff::changed`],
+/// [`PublicApiDiff::added`] and [`PublicApiDiff::moved`] that differs, lists
+/// the items that are only in `a` and only in `b`.
+#[must_use]
+pub fn describe_difference(a: &PublicApiDiff, b: &PublicApiDiff) -> Option<String> {
+    if a == b {
+        return None;
+    }
+
+    let render_item = |item: &PublicItem| item.to_string();
+    let render_changed = |item: &ChangedPublicItem| format!("{} -> {}", item.old, item.new);
+    let render_moved = |(old, new): &(PublicItem, PublicItem)| format!("{old} -> {new}");
+
+    let mut output = String::new();
+    describe_bucket(&mut output, "removed", &a.removed, &b.removed, render_item);
+    describe_bucket(
+        &mut output,
+        "changed",
+        &a.changed,
+        &b.changed,
+        render_changed,
+    );
+    describe_bucket(&mut output, "added", &a.added, &b.added, render_item);
+    describe_bucket(&mut output, "moved", &a.moved, &b.moved, render_moved);
+    Some(output)
+}
+
+/// Helper for [`describe_difference()`] that describes how one bucket of the
+/// two diffs differs, if it does.
+fn describe_bucket<T: PartialEq>(
+    output: &mut String,
+    name: &str,
+    a: &[T],
+    b: &[T],
+    render: impl Fn(&T) -> String,
+) {
+    use std::fmt::Write;
+
+    if a == b {
+        return;
+    }
+
+    writeln!(output, "{name}:").unwrap();
+    let only_in_a = a
+        .iter()
+        .filter(|item| !b.contains(item))
+        .collect::<Vec<_>>();
+    let only_in_b = b
+        .iter()
+        .filter(|item| !a.contains(item))
+        .collect::<Vec<_>>();
+    for &item in &only_in_a {
+        writeln!(output, "  only in first: {}", render(item)).unwrap();
+    }
+    for &item in &only_in_b {
+        writeln!(output, "  only in second: {}", render(item)).unwrap();
+    }
+    if only_in_a.is_empty() && only_in_b.is_empty() {
+        writeln!(output, "  same items, but in a different order or number").unwrap();
+    }
+}
+
+/// A single workflow command for [`to_github_annotations()`].
+fn github_annotation(level: AnnotationLevel, item: &PublicItem, title: &str) -> String {
+    let command = match level {
+        AnnotationLevel::Notice => "notice",
+        AnnotationLevel::Warning => "warning",
+        AnnotationLevel::Error => "error",
+    };
+    let mut properties = vec![];
+    if let Some((file, line)) = item.span() {
+        properties.push(format!(
+            "file={}",
+            escape_annotation_property(&file.to_string_lossy())
+        ));
+        properties.push(format!("line={line}"));
+    }
+    properties.push(format!("title={}", escape_annotation_property(title)));
+
+    format!(
+        "::{command} {}::{}",
+        properties.join(","),
+        escape_annotation_data(&item.to_string())
+    )
+}
+
+/// Escapes the message of a workflow command, like `@actions/core` does.
+fn escape_annotation_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a workflow command, like `@actions/core` does.
+/// Commas and colons would otherwise end the value.
+fn escape_annotation_property(property: &str) -> String {
+    escape_annotation_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Maps each [`ItemKind`] to the items of that kind, preserving item order.
+fn group_by_kind(items: &[PublicItem]) -> BTreeMap<ItemKind, Vec<&PublicItem>> {
+    let mut map: BTreeMap<ItemKind, Vec<&PublicItem>> = BTreeMap::new();
+    for item in items {
+        map.entry(item.kind()).or_default().push(item);
+    }
+    map
+}
+
+/// Converts a set (read: bag) of public items into a hash map that maps a given
+/// path to a vec of public items with that path.
+fn bag_to_path_map<'a>(
+    difference: impl Iterator<Item = (&'a (bool, PublicItem), usize)>,
+) -> ItemsWithPath {
+    let mut map: ItemsWithPath = HashMap::new();
+    for ((_, item), occurrences) in difference {
+        let items = map.entry(item.sortable_path.clone()).or_default();
+        for _ in 0..occurrences {
+            items.push(item.clone());
+        }
+    }
+    map
+}
+
+/// The modules that an item is in, e.g. `["004-krate", "004-old"]` for
+/// `krate::old::Foo::field`. Modules have the sorting prefix
+/// [`MODULE_SORTING_PREFIX`], see [`crate::item_processor::sorting_prefix()`].
+fn module_path(item: &PublicItem) -> &[String] {
+    let module_prefix = formatted_sorting_prefix(MODULE_SORTING_PREFIX);
+    let parents = &item.sortable_path[..item.sortable_path.len().saturating_sub(1)];
+    let len = parents
+        .iter()
+        .take_while(|component| component.starts_with(&module_prefix))
+        .count();
+    &parents[..len]
+}
+
+/// The kind, path and rendering of an item with its module path removed. Items
+/// with the same key but different [`module_path`]s have been moved.
+fn moved_key(item: &PublicItem) -> MovedKey {
+    let modules = module_path(item);
+    let rest = &item.sortable_path[modules.len()..];
+    let rendered = item.to_string();
+
+    let prefix: String = modules
+        .iter()
+        .map(|module| format!("{}::", split_sorting_prefix(module).1))
+        .collect();
+    if prefix.is_empty() {
+        return (item.kind, rest.to_vec(), rendered);
+    }
+
+    (
+        item.kind,
+        rest.iter()
+            .map(|component| component.replace(&prefix, ""))
+            .collect(),
+        rendered.replace(&prefix, ""),
+    )
+}
+
+fn moved_grouping_cmp(
+    a: &(PublicItem, PublicItem),
+    b: &(PublicItem, PublicItem),
+) -> std::cmp::Ordering {
+    match PublicItem::grouping_cmp(&a.0, &b.0) {
+        std::cmp::Ordering::Equal => PublicItem::grouping_cmp(&a.1, &b.1),
+        ordering => ordering,
+    }
+}
+
+/// The tokens of a function split up by [`split_fn_generic_params`].
+struct FnGenericParams<'a> {
+    /// The tokens before the generic parameters, up to and including `<`
+    prefix: &'a [Token],
+
+    /// The generic parameters themselves, without whitespace
+    params: Vec<Vec<&'a Token>>,
+
+    /// The tokens after the generic parameters, i.e. after `>`
+    suffix: &'a [Token],
+}
+
+/// Splits the tokens of a function into the tokens before its generic
+/// parameters, the generic parameters themselves, and the tokens after them.
+/// Returns `None` if the function has no generic parameters.
+fn split_fn_generic_params(tokens: &[Token]) -> Option<FnGenericParams<'_>> {
+    let start = 1 + tokens
+        .windows(2)
+        .position(|w| matches!(&w[0], Token::Function(_)) && w[1] == Token::symbol("<"))?;
+
+    let mut depth = 0;
+    let mut params = vec![];
+    let mut param_start = start + 1;
+    for (index, token) in tokens.iter().enumerate().skip(start) {
+        let Token::Symbol(symbol) = token else {
+            continue;
+        };
+        match symbol.as_str() {
+            "<" => depth += 1,
+            "," if depth == 1 => {
+                params.push(without_whitespace(&tokens[param_start..index]));
+                param_start = index + 1;
+            }
+            ">" => {
+                depth -= 1;
+                if depth == 0 {
+                    params.push(without_whitespace(&tokens[param_start..index]));
+                    return Some(FnGenericParams {
+                        prefix: &tokens[..start],
+                        params,
+                        suffix: &tokens[index + 1..],
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Splits the tokens of a function into its parameters and its return type.
+/// Returns `None` if the tokens do not look like a function.
+fn split_fn_signature(tokens: &[Token]) -> Option<(Vec<Param>, String)> {
+    let name = tokens
+        .iter()
+        .position(|token| matches!(token, Token::Function(_)))?;
+    let start = name
+        + tokens[name..]
+            .iter()
+            .position(|token| *token == Token::symbol("("))?;
+    let mut depth = 0;
+    let mut params = vec![];
+    let mut param_start = start + 1;
+    let mut end = None;
+    for (index, token) in tokens.iter().enumerate().skip(start) {
+        let Token::Symbol(symbol) = token else {
+            continue;
+        };
+        match symbol.as_str() {
+            "(" | "<" | "[" => depth += 1,
+            "," if depth == 1 => {
+                params.push(param_from_tokens(&tokens[param_start..index]));
+                param_start = index + 1;
+            }
+            ")" | ">" | "]" => {
+                depth -= 1;
+                if depth == 0 {
+                    if !without_whitespace(&tokens[param_start..index]).is_empty() {
+                        params.push(param_from_tokens(&tokens[param_start..index]));
+                    }
+                    end = Some(index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let rest = &tokens[end? + 1..];
+    let return_type = match rest.iter().position(|token| *token == Token::symbol("->")) {
+        Some(arrow) => {
+            let return_type = &rest[arrow + 1..];
+            let where_ = return_type
+                .iter()
+                .position(|token| *token == Token::keyword("where"))
+                .unwrap_or(return_type.len());
+            tokens_to_string(&return_type[..where_]).trim().to_owned()
+        }
+        None => String::from("()"),
+    };
+
+    Some((params, return_type))
+}
+
+/// Parses e.g. `x: usize`, `&mut self` or `self: Box<Self>` into a [`Param`].
+fn param_from_tokens(tokens: &[Token]) -> Param {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        match token.text() {
+            "<" | "(" | "[" => depth += 1,
+            ">" | ")" | "]" => depth -= 1,
+            ":" if depth == 0 => {
+                return Param {
+                    name: tokens_to_string(&tokens[..index]).trim().to_owned(),
+                    type_: tokens_to_string(&tokens[index + 1..]).trim().to_owned(),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    match tokens.split_last() {
+        Some((Token::Self_(_), receiver)) => Param {
+            name: String::from("self"),
+            type_: format!("{}Self", tokens_to_string(receiver).trim_start()),
+        },
+        _ => Param {
+            name: String::from("_"),
+            type_: tokens_to_string(tokens).trim().to_owned(),
+        },
+    }
+}
+
+fn without_whitespace(tokens: &[Token]) -> Vec<&Token> {
+    tokens.iter().filter(|t| **t != Token::Whitespace).collect()
+}
+
+/// `true` if `old` and `new` are the same function except that its generic
+/// parameters are in a different order.
+fn generic_params_reordered(old: &[Token], new: &[Token]) -> bool {
+    let (Some(mut old), Some(mut new)) =
+        (split_fn_generic_params(old), split_fn_generic_params(new))
+    else {
+        return false;
+    };
+
+    if old.prefix != new.prefix || old.suffix != new.suffix || old.params == new.params {
+        return false;
+    }
+
+    old.params.sort();
+    new.params.sort();
+    old.params == new.params
+}
+
+/// `item` with the renames of [`PublicApiDiff::between_with_renames()`]
+/// applied to its sortable path and its tokens.
+fn renamed_item(mut item: PublicItem, renames: &[(Vec<String>, Vec<String>)]) -> PublicItem {
+    let renames = renames
+        .iter()
+        .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+        .collect::<Vec<_>>();
+
+    let names = item
+        .sortable_path
+        .iter()
+        .map(|component| split_sorting_prefix(component).1)
+        .collect::<Vec<_>>();
+    let starts_with = |from: &[String]| {
+        names.len() >= from.len()
+            && names
+                .iter()
+                .zip(from)
+                .all(|(name, from)| *name == from.as_str())
+    };
+    if let Some((from, to)) = renames.iter().find(|(from, _)| starts_with(from)) {
+        // New components get the sorting prefix of the component they replace
+        let prefix_of =
+            |index: usize| split_sorting_prefix(&item.sortable_path[index.min(from.len() - 1)]).0;
+        let renamed_prefix = to
+            .iter()
+            .enumerate()
+            .map(|(index, name)| format!("{}{name}", prefix_of(index)));
+        item.sortable_path = renamed_prefix
+            .chain(item.sortable_path[from.len()..].iter().cloned())
+            .collect();
+    }
+    // Components of impls are rendered impls, which can contain paths too
+    for component in &mut item.sortable_path {
+        for (from, to) in &renames {
+            *component = renamed_in_text(component, from, to);
+        }
+    }
+
+    item.tokens = renamed_in_tokens(&item.tokens, &renames);
+    item
+}
+
+/// The tokens of an item without its `#[must_use]` and `#[deprecated]`
+/// attributes, see [`ChangeCategory::MustUseOrDeprecationChanged`].
+fn without_non_breaking_attributes(tokens: &[Token]) -> Vec<&Token> {
+    let mut output = vec![];
+    let mut tokens = tokens.iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Annotation(attr)
+                if attr.starts_with("#[must_use") || attr.starts_with("#[deprecated") =>
+            {
+                // Attributes are always followed by whitespace, skip it too
+                tokens.next();
+            }
+            _ => output.push(token),
+        }
+    }
+    output
+}
+
+/// Replaces paths in `text` that start with `from` so that they start with
+/// `to` instead. Only whole path components match, so renaming `krate::a`
+/// leaves `krate::ab` and `other::krate::a` alone.
+fn renamed_in_text(text: &str, from: &[String], to: &[String]) -> String {
+    let from = from.join("::");
+    let to = to.join("::");
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(&from) {
+        let (before, after) = (&rest[..index], &rest[index + from.len()..]);
+        let starts_path = !before.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == ':');
+        let ends_component = !after.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        output.push_str(before);
+        output.push_str(if starts_path && ends_component {
+            &to
+        } else {
+            &from
+        });
+        rest = after;
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Replaces paths in `tokens` that start with `from` so that they start with
+/// `to` instead. A path is a run of tokens separated by `::` symbols. The last
+/// component of `to` keeps the kind of token of the last component of `from`,
+/// e.g. [`Token::Type`], the other components become [`Token::Identifier`]s.
+fn renamed_in_tokens(tokens: &[Token], renames: &[&(Vec<String>, Vec<String>)]) -> Vec<Token> {
+    let is_separator = |token: &Token| token == &Token::symbol("::");
+    let matches_at = |start: usize, from: &[String]| {
+        from.iter().enumerate().all(|(index, name)| {
+            let separated =
+                index == 0 || tokens.get(start + 2 * index - 1).is_some_and(is_separator);
+            separated
+                && tokens
+                    .get(start + 2 * index)
+                    .is_some_and(|token| token.text() == name.as_str())
+        })
+    };
+
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut index = 0;
+    while index < tokens.len() {
+        let starts_path = index == 0 || !is_separator(&tokens[index - 1]);
+        let rename = renames
+            .iter()
+            .find(|(from, _)| starts_path && matches_at(index, from));
+        let Some((from, to)) = rename else {
+            output.push(tokens[index].clone());
+            index += 1;
+            continue;
+        };
+
+        let last = &tokens[index + 2 * (from.len() - 1)];
+        for (position, name) in to.iter().enumerate() {
+            if position > 0 {
+                output.push(Token::symbol("::"));
+            }
+            output.push(if position == to.len() - 1 {
+                with_text(last, name)
+            } else {
+                Token::identifier(name)
+            });
+        }
+        index += 2 * from.len() - 1;
+    }
+    output
+}
+
+/// A token of the same kind as `token`, but with `text`.
+fn with_text(token: &Token, text: &str) -> Token {
+    let text = text.to_owned();
+    match token {
+        Token::Symbol(_) => Token::Symbol(text),
+        Token::Qualifier(_) => Token::Qualifier(text),
+        Token::Kind(_) => Token::Kind(text),
+        Token::Whitespace => Token::Whitespace,
+        Token::Identifier(_) => Token::Identifier(text),
+        Token::Annotation(_) => Token::Annotation(text),
+        Token::Self_(_) => Token::Self_(text),
+        Token::Function(_) => Token::Function(text),
+        Token::Lifetime(_) => Token::Lifetime(text),
+        Token::Keyword(_) => Token::Keyword(text),
+        Token::Generic(_) => Token::Generic(text),
+        Token::Primitive(_) => Token::Primitive(text),
+        Token::Type(_) => Token::Type(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_and_only_item_removed() {
+        let old = api([item_with_path("foo")]);
+        let new = api([]);
+
+        let actual = PublicApiDiff::between(old, new);
+        let expected = PublicApiDiff {
+            removed: vec![item_with_path("foo")],
+            changed: vec![],
+            added: vec![],
+            moved: vec![],
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_empty());
+    }
+
+    #[test]
+    fn single_and_only_item_added() {
+        let old = api([]);
+        let new = api([item_with_path("foo")]);
+
+        let actual = PublicApiDiff::between(old, new);
+        let expected = PublicApiDiff {
+            removed: vec![],
+            changed: vec![],
+            added: vec![item_with_path("foo")],
+            moved: vec![],
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_empty());
+    }
+
+    #[test]
+    fn middle_item_added() {
+        let old = api([item_with_path("1"), item_with_path("3")]);
+        let new = api([
+            item_with_path("1"),
+            item_with_path("2"),
+            item_with_path("3"),
+        ]);
+
+        let actual = PublicApiDiff::between(old, new);
+        let expected = PublicApiDiff {
+            removed: vec![],
+            changed: vec![],
+            added: vec![item_with_path("2")],
+            moved: vec![],
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_empty());
+    }
+
+    #[test]
+    fn middle_item_removed() {
+        let old = api([
+            item_with_path("1"),
+            item_with_path("2"),
+            item_with_path("3"),
+        ]);
+        let new = api([item_with_path("1"), item_with_path("3")]);
+
+        let actual = PublicApiDiff::between(old, new);
+        let expected = PublicApiDiff {
+            removed: vec![item_with_path("2")],
+            changed: vec![],
+            added: vec![],
+            moved: vec![],
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_empty());
+    }
+
+    #[test]
+    fn many_identical_items() {
+        let old = api([
+            item_with_path("1"),
+            item_with_path("2"),
+            item_with_path("2"),
+            item_with_path("3"),
+            item_with_path("3"),
+            item_with_path("3"),
+            fn_with_param_type(&["a", "b"], "i32"),
+            fn_with_param_type(&["a", "b"], "i32"),
+        ]);
+        let new = api([
+            item_with_path("1"),
+            item_with_path("2"),
             item_with_path("3"),
             item_with_path("4"),
             item_with_path("4"),
@@ -254,6 +1584,7 @@ mod tests {
                 },
             ],
             added: vec![item_with_path("4"), item_with_path("4")],
+            moved: vec![],
         };
         assert_eq!(actual, expected);
         assert!(!actual.is_empty());
@@ -281,6 +1612,7 @@ mod tests {
             removed: vec![],
             changed: vec![],
             added: vec![fn_with_param_type(&["a", "b"], "u8")],
+            moved: vec![],
         };
         let actual = PublicApiDiff::between(old, new);
         assert_eq!(actual, expected);
@@ -297,11 +1629,780 @@ mod tests {
             removed: vec![],
             changed: vec![],
             added: vec![],
+            moved: vec![],
         };
         assert_eq!(actual, expected);
         assert!(actual.is_empty());
     }
 
+    #[test]
+    fn added_and_removed_grouped_by_kind() {
+        let old = api([
+            with_kind(item_with_path("a::S1"), ItemKind::Struct),
+            with_kind(item_with_path("a::S2"), ItemKind::Struct),
+            with_kind(item_with_path("a::f"), ItemKind::Function),
+        ]);
+        let new = api([
+            with_kind(item_with_path("a::f"), ItemKind::Function),
+            with_kind(item_with_path("a::g"), ItemKind::Function),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+
+        let removed = diff.removed_by_kind();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(
+            removed[&ItemKind::Struct],
+            vec![&item_with_path("a::S1"), &item_with_path("a::S2")]
+        );
+
+        let added = diff.added_by_kind();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[&ItemKind::Function], vec![&item_with_path("a::g")]);
+    }
+
+    #[test]
+    fn targets_are_labeled() {
+        let mut linux = api([item_with_path("common"), item_with_path("linux_only")]);
+        linux.target = Some(String::from("x86_64-unknown-linux-gnu"));
+        let mut windows = api([item_with_path("common"), item_with_path("windows_only")]);
+        windows.target = Some(String::from("x86_64-pc-windows-msvc"));
+
+        let actual = diff_targets(linux, windows);
+        let expected = TargetsDiff {
+            first_target: Some(String::from("x86_64-unknown-linux-gnu")),
+            second_target: Some(String::from("x86_64-pc-windows-msvc")),
+            only_in_first: vec![item_with_path("linux_only")],
+            differing: vec![],
+            only_in_second: vec![item_with_path("windows_only")],
+        };
+        assert_eq!(actual, expected);
+        assert!(!actual.is_empty());
+    }
+
+    #[test]
+    fn difference_between_diffs() {
+        let diff = |old, new| PublicApiDiff::between(api(old), api(new));
+        let a = diff(vec![item_with_path("a")], vec![item_with_path("b")]);
+        let b = diff(vec![item_with_path("a")], vec![item_with_path("c")]);
+
+        assert_eq!(describe_difference(&a, &a.clone()), None);
+        assert_eq!(
+            describe_difference(&a, &b).unwrap(),
+            "added:\n  only in first: b\n  only in second: c\n"
+        );
+
+        let twice = diff(vec![], vec![item_with_path("b"), item_with_path("b")]);
+        let once = diff(vec![], vec![item_with_path("b")]);
+        assert_eq!(
+            describe_difference(&twice, &once).unwrap(),
+            "added:\n  same items, but in a different order or number\n"
+        );
+    }
+
+    #[test]
+    fn unified_text() {
+        let old = api([
+            item_with_path("a"),
+            fn_with_param_type(&["b"], "i32"),
+            item_with_path("c"),
+        ]);
+        let new = api([
+            fn_with_param_type(&["b"], "i64"),
+            item_with_path("c"),
+            item_with_path("d"),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(
+            to_unified(&diff),
+            "-a\n-pub fn b(x: i32)\n+pub fn b(x: i64)\n+d\n"
+        );
+        assert_eq!(to_unified(&PublicApiDiff::between(api([]), api([]))), "");
+    }
+
+    #[test]
+    fn side_by_side_rows() {
+        let old = api([
+            item_with_path("a"),
+            fn_with_param_type(&["b"], "i32"),
+            item_with_path("c"),
+        ]);
+        let new = api([
+            fn_with_param_type(&["b"], "i64"),
+            item_with_path("c"),
+            item_with_path("ab"),
+        ]);
+
+        let row =
+            |old: Option<&str>, new: Option<&str>| (old.map(String::from), new.map(String::from));
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(
+            side_by_side(&diff),
+            vec![
+                row(Some("a"), None),
+                row(None, Some("ab")),
+                row(Some("pub fn b(x: i32)"), Some("pub fn b(x: i64)")),
+            ]
+        );
+        assert!(side_by_side(&PublicApiDiff::between(api([]), api([]))).is_empty());
+    }
+
+    #[test]
+    fn labeled_lines() {
+        let old = api([item_with_path("a"), fn_with_param_type(&["b"], "i32")]);
+        let new = api([fn_with_param_type(&["b"], "i64"), item_with_path("c")]);
+        let diff = PublicApiDiff::between(old, new);
+
+        let labels = DiffLabels::default();
+        assert_eq!(render_removed(&diff, &labels), vec!["REMOVED: a"]);
+        assert_eq!(render_added(&diff, &labels), vec!["ADDED: c"]);
+        assert_eq!(
+            render_changed(&diff, &labels),
+            vec![
+                "CHANGED FROM: pub fn b(x: i32)",
+                "CHANGED TO: pub fn b(x: i64)",
+            ]
+        );
+
+        let labels = DiffLabels {
+            removed: String::from("Entfernt"),
+            ..DiffLabels::default()
+        };
+        assert_eq!(render_removed(&diff, &labels), vec!["Entfernt: a"]);
+    }
+
+    #[test]
+    fn variant_added_to_non_exhaustive_enum_is_minor() {
+        let old = api([item_with_path("E::A")]);
+        let new = api([item_with_path("E::A"), item_with_path("E::B")]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.breaking_additions().count(), 0);
+        assert_eq!(diff.semver_impact(), SemverImpact::Minor);
+    }
+
+    #[test]
+    fn variant_added_to_exhaustive_enum_is_major() {
+        let old = api([in_exhaustive_type(item_with_path("E::A"))]);
+        let new = api([
+            in_exhaustive_type(item_with_path("E::A")),
+            in_exhaustive_type(item_with_path("E::B")),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(
+            diff.breaking_additions().collect::<Vec<_>>(),
+            vec![&item_with_path("E::B")]
+        );
+        assert_eq!(diff.semver_impact(), SemverImpact::Major);
+    }
+
+    #[test]
+    fn field_added_to_non_exhaustive_struct_is_minor() {
+        let old = api([with_kind(item_with_path("S::a"), ItemKind::StructField)]);
+        let new = api([
+            with_kind(item_with_path("S::a"), ItemKind::StructField),
+            with_kind(item_with_path("S::b"), ItemKind::StructField),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.semver_impact(), SemverImpact::Minor);
+    }
+
+    #[test]
+    fn field_added_to_exhaustive_struct_is_major() {
+        let old = api([in_exhaustive_type(with_kind(
+            item_with_path("S::a"),
+            ItemKind::StructField,
+        ))]);
+        let new = api([
+            in_exhaustive_type(with_kind(item_with_path("S::a"), ItemKind::StructField)),
+            in_exhaustive_type(with_kind(item_with_path("S::b"), ItemKind::StructField)),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.breaking_additions().count(), 1);
+        assert_eq!(diff.semver_impact(), SemverImpact::Major);
+    }
+
+    #[test]
+    fn new_exhaustive_enum_is_minor() {
+        let old = api([]);
+        let new = api([
+            with_kind(item_with_path("E"), ItemKind::Enum),
+            in_exhaustive_type(with_kind(item_with_path("E::A"), ItemKind::Variant)),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.breaking_additions().count(), 0);
+        assert_eq!(diff.non_breaking_additions().count(), 2);
+        assert_eq!(diff.semver_impact(), SemverImpact::Minor);
+    }
+
+    #[test]
+    fn new_exhaustive_struct_is_minor() {
+        let old = api([]);
+        let new = api([
+            with_kind(item_with_path("S"), ItemKind::Struct),
+            in_exhaustive_type(with_kind(item_with_path("S::a"), ItemKind::StructField)),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.breaking_additions().count(), 0);
+        assert_eq!(diff.semver_impact(), SemverImpact::Minor);
+    }
+
+    #[test]
+    fn new_trait_with_required_method_is_minor() {
+        let old = api([]);
+        let new = api([
+            with_kind(item_with_path("T"), ItemKind::Trait),
+            in_exhaustive_type(with_kind(item_with_path("T::m"), ItemKind::Method)),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.breaking_additions().count(), 0);
+        assert!(breaking_changes(&diff).is_empty());
+        assert_eq!(diff.semver_impact(), SemverImpact::Minor);
+    }
+
+    #[test]
+    fn breaking_changes_with_reasons() {
+        let old = api([
+            fn_with_param_type(&["a"], "i32"),
+            fn_with_param_type(&["b"], "i32"),
+            with_kind(item_with_path("T::m"), ItemKind::Method),
+        ]);
+        let new = api([
+            fn_with_param_type(&["b"], "i64"),
+            with_kind(item_with_path("T::m"), ItemKind::Method),
+            in_exhaustive_type(with_kind(item_with_path("T::n"), ItemKind::Method)),
+            with_kind(item_with_path("T::provided"), ItemKind::Method),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        let changes = breaking_changes(&diff);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.item.to_string(), c.category, c.reason.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    String::from("pub fn a(x: i32)"),
+                    BreakingChangeCategory::Removed,
+                    "public function removed"
+                ),
+                (
+                    String::from("pub fn b(x: i32)"),
+                    BreakingChangeCategory::Changed,
+                    "signature of public function changed"
+                ),
+                (
+                    String::from("T::n"),
+                    BreakingChangeCategory::Added,
+                    "required trait method added"
+                ),
+            ]
+        );
+        assert!(diff.is_breaking());
+
+        let additive = PublicApiDiff::between(api([]), api([item_with_path("a")]));
+        assert!(breaking_changes(&additive).is_empty());
+        assert!(!additive.is_breaking());
+    }
+
+    #[test]
+    fn min_compatible_version_stops_at_first_breaking_baseline() {
+        let current = vec![item_with_path("a"), item_with_path("b")];
+        let baselines = vec![
+            (3, vec![item_with_path("a"), item_with_path("b")]),
+            (1, vec![item_with_path("a"), item_with_path("c")]),
+            (2, vec![item_with_path("a")]),
+        ];
+        assert_eq!(min_compatible_version(&current, &baselines), Some(2));
+
+        // Compatible with 1, but not with the newer 2, so `current` can't
+        // claim compatibility with 1 either
+        let baselines = vec![
+            (1, vec![item_with_path("a")]),
+            (2, vec![item_with_path("a"), item_with_path("c")]),
+        ];
+        assert_eq!(min_compatible_version(&current, &baselines), None);
+
+        assert_eq!(min_compatible_version::<u32>(&current, &[]), None);
+    }
+
+    #[test]
+    fn recommend_version_after_1_0_0() {
+        let major = PublicApiDiff::between(api([item_with_path("a")]), api([]));
+        let minor = PublicApiDiff::between(api([]), api([item_with_path("a")]));
+        let patch = PublicApiDiff::between(api([]), api([]));
+
+        assert_eq!(recommend_version("1.2.3", &major), "2.0.0");
+        assert_eq!(recommend_version("1.2.3", &minor), "1.3.0");
+        assert_eq!(recommend_version("1.2.3", &patch), "1.2.4");
+    }
+
+    #[test]
+    fn recommend_version_before_1_0_0() {
+        let major = PublicApiDiff::between(api([item_with_path("a")]), api([]));
+        let minor = PublicApiDiff::between(api([]), api([item_with_path("a")]));
+        let patch = PublicApiDiff::between(api([]), api([]));
+
+        assert_eq!(recommend_version("0.2.3", &major), "0.3.0");
+        assert_eq!(recommend_version("0.2.3", &minor), "0.2.4");
+        assert_eq!(recommend_version("0.2.3", &patch), "0.2.4");
+
+        // Every 0.0.z release is breaking
+        assert_eq!(recommend_version("0.0.3", &major), "0.0.4");
+        assert_eq!(recommend_version("0.0.3", &minor), "0.0.4");
+        assert_eq!(recommend_version("0.0.3", &patch), "0.0.4");
+    }
+
+    #[test]
+    fn recommend_version_drops_pre_release_and_keeps_invalid_versions() {
+        let major = PublicApiDiff::between(api([item_with_path("a")]), api([]));
+
+        assert_eq!(recommend_version("1.2.3-alpha.1+build", &major), "2.0.0");
+        assert_eq!(recommend_version("1.2", &major), "1.2");
+        assert_eq!(recommend_version("1.2.3.4", &major), "1.2.3.4");
+        assert_eq!(recommend_version("latest", &major), "latest");
+    }
+
+    #[test]
+    fn semver_impact_of_removal_and_no_change() {
+        let diff = PublicApiDiff::between(api([item_with_path("foo")]), api([]));
+        assert_eq!(diff.semver_impact(), SemverImpact::Major);
+
+        let diff =
+            PublicApiDiff::between(api([item_with_path("foo")]), api([item_with_path("foo")]));
+        assert_eq!(diff.semver_impact(), SemverImpact::Patch);
+    }
+
+    #[test]
+    fn try_between_same_crate() {
+        let old = api_of_crate("foo", [item_with_path("foo::a")]);
+        let new = api_of_crate("foo", [item_with_path("foo::b")]);
+
+        let diff = PublicApiDiff::try_between(old, new).unwrap();
+        assert_eq!(diff.removed, vec![item_with_path("foo::a")]);
+        assert_eq!(diff.added, vec![item_with_path("foo::b")]);
+    }
+
+    #[test]
+    fn try_between_unknown_crate_name() {
+        let old = api_of_crate("foo", [item_with_path("foo::a")]);
+        let new = api([item_with_path("foo::a")]);
+
+        assert!(PublicApiDiff::try_between(old, new).unwrap().is_empty());
+    }
+
+    #[test]
+    fn try_between_different_crates() {
+        let old = api_of_crate("foo", [item_with_path("foo::a")]);
+        let new = api_of_crate("bar", [item_with_path("bar::a")]);
+
+        let err = PublicApiDiff::try_between(old, new).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CrateNameMismatch { old, new } if old == "foo" && new == "bar"
+        ));
+    }
+
+    #[test]
+    fn between_index_same_as_between() {
+        let old = || api([item_with_path("foo"), item_with_path("bar")]);
+        let new = || api([item_with_path("foo"), item_with_path("baz")]);
+
+        let index = ApiIndex::new(old());
+        assert_eq!(
+            PublicApiDiff::between_index(&index, new()).unwrap(),
+            PublicApiDiff::between(old(), new())
+        );
+    }
+
+    #[test]
+    fn between_index_rejects_stale_index() {
+        let mut index = ApiIndex::new(api([item_with_path("foo")]));
+        index.format_version = String::from("0.0.1");
+
+        let err = PublicApiDiff::between_index(&index, api([])).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::StaleApiIndex { index_version, .. } if index_version == "0.0.1"
+        ));
+    }
+
+    #[test]
+    fn api_index_serde_roundtrip() {
+        let index = ApiIndex::new(api([
+            with_kind(item_with_path("S"), ItemKind::Struct),
+            with_kind(item_with_path("S::a"), ItemKind::StructField),
+        ]));
+
+        let json = serde_json::to_string(&index).unwrap();
+        let deserialized: ApiIndex = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, index);
+        assert_eq!(
+            deserialized
+                .items()
+                .map(PublicItem::kind)
+                .collect::<Vec<_>>(),
+            vec![ItemKind::Struct, ItemKind::StructField]
+        );
+    }
+
+    #[test]
+    fn only_additions_is_not_breaking() {
+        let diff = PublicApiDiff::between(
+            api([item_with_path("foo")]),
+            api([item_with_path("foo"), item_with_path("bar")]),
+        );
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn removal_is_breaking() {
+        let diff = PublicApiDiff::between(
+            api([item_with_path("foo"), item_with_path("bar")]),
+            api([item_with_path("foo")]),
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn change_is_breaking() {
+        let diff = PublicApiDiff::between(
+            api([fn_with_param_type(&["a", "b"], "i32")]),
+            api([fn_with_param_type(&["a", "b"], "i64")]),
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn no_diff_is_not_breaking() {
+        let diff =
+            PublicApiDiff::between(api([item_with_path("foo")]), api([item_with_path("foo")]));
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn reordered_generic_params() {
+        let diff = PublicApiDiff::between(
+            api([fn_with_generic_params(&["T", "U"], "T")]),
+            api([fn_with_generic_params(&["U", "T"], "T")]),
+        );
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].category(),
+            ChangeCategory::GenericParamsReordered
+        );
+    }
+
+    #[test]
+    fn changed_generic_params_are_not_reordered() {
+        let diff = PublicApiDiff::between(
+            api([fn_with_generic_params(&["T", "U"], "T")]),
+            api([fn_with_generic_params(&["T", "V"], "T")]),
+        );
+        assert_eq!(diff.changed[0].category(), ChangeCategory::Other);
+    }
+
+    #[test]
+    fn reordered_generic_params_and_changed_signature() {
+        let diff = PublicApiDiff::between(
+            api([fn_with_generic_params(&["T", "U"], "T")]),
+            api([fn_with_generic_params(&["U", "T"], "U")]),
+        );
+        assert_eq!(diff.changed[0].category(), ChangeCategory::Other);
+    }
+
+    #[test]
+    fn changed_signature_without_generic_params() {
+        let diff = PublicApiDiff::between(
+            api([fn_with_param_type(&["a", "b"], "i32")]),
+            api([fn_with_param_type(&["a", "b"], "i64")]),
+        );
+        assert_eq!(diff.changed[0].category(), ChangeCategory::Other);
+    }
+
+    #[test]
+    fn merge_equals_diff_of_combined_apis() {
+        let a_old = [
+            item_with_path("a::removed"),
+            fn_with_param_type(&["a", "f"], "i32"),
+        ];
+        let a_new = [
+            item_with_path("a::added"),
+            fn_with_param_type(&["a", "f"], "i64"),
+        ];
+        let b_old = [item_with_path("b::removed"), item_with_path("b::kept")];
+        let b_new = [item_with_path("b::added"), item_with_path("b::kept")];
+
+        // Merge in the "wrong" order to make sure the result is re-sorted
+        let merged = PublicApiDiff::between(api(b_old.clone()), api(b_new.clone())).merge(
+            PublicApiDiff::between(api(a_old.clone()), api(a_new.clone())),
+        );
+        let combined = PublicApiDiff::between(
+            api(a_old.into_iter().chain(b_old)),
+            api(a_new.into_iter().chain(b_new)),
+        );
+
+        assert_eq!(merged, combined);
+        assert_eq!(merged.removed.len(), 2);
+        assert_eq!(merged.changed.len(), 1);
+        assert_eq!(merged.added.len(), 2);
+    }
+
+    #[test]
+    fn moved_items_are_only_detected_when_asked_for() {
+        let old = api([
+            struct_in_module(&["krate", "old"], "Foo"),
+            struct_in_module(&["krate"], "Bar"),
+        ]);
+        let new = api([
+            struct_in_module(&["krate", "new"], "Foo"),
+            struct_in_module(&["krate", "new"], "Bar"),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.removed.len(), 2);
+        assert_eq!(diff.added.len(), 2);
+        assert!(diff.moved.is_empty());
+
+        let diff = diff.detect_moved_items();
+        assert!(diff.removed.is_empty());
+        assert!(diff.added.is_empty());
+        assert_eq!(
+            diff.moved,
+            vec![
+                (
+                    struct_in_module(&["krate", "old"], "Foo"),
+                    struct_in_module(&["krate", "new"], "Foo"),
+                ),
+                (
+                    struct_in_module(&["krate"], "Bar"),
+                    struct_in_module(&["krate", "new"], "Bar"),
+                ),
+            ]
+        );
+        assert!(diff.is_breaking());
+    }
+
+    #[test]
+    fn renamed_item_is_not_moved() {
+        let old = api([struct_in_module(&["krate", "old"], "Foo")]);
+        let new = api([struct_in_module(&["krate", "new"], "Bar")]);
+
+        let diff = PublicApiDiff::between(old, new).detect_moved_items();
+        assert_eq!(
+            diff.removed,
+            vec![struct_in_module(&["krate", "old"], "Foo")]
+        );
+        assert_eq!(diff.added, vec![struct_in_module(&["krate", "new"], "Bar")]);
+        assert!(diff.moved.is_empty());
+    }
+
+    #[test]
+    fn renamed_module_is_not_removed_and_added() {
+        let old = || {
+            api([
+                struct_in_module(&["krate", "old"], "Foo"),
+                struct_in_module(&["krate", "older"], "Bar"),
+            ])
+        };
+        let new = || {
+            api([
+                struct_in_module(&["krate", "new", "inner"], "Foo"),
+                struct_in_module(&["krate", "older"], "Bar"),
+            ])
+        };
+        let renames = [(
+            vec![String::from("krate"), String::from("old")],
+            vec![
+                String::from("krate"),
+                String::from("new"),
+                String::from("inner"),
+            ],
+        )];
+
+        let diff = PublicApiDiff::between(old(), new());
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.added.len(), 1);
+
+        let diff = PublicApiDiff::between_with_renames(old(), new(), &renames);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn renamed_item_that_also_changed() {
+        let old = api([fn_with_param_type(&["krate", "old", "f"], "i32")]);
+        let new = api([fn_with_param_type(&["krate", "new", "g"], "i64")]);
+        let renames = [(
+            vec![
+                String::from("krate"),
+                String::from("old"),
+                String::from("f"),
+            ],
+            vec![
+                String::from("krate"),
+                String::from("new"),
+                String::from("g"),
+            ],
+        )];
+
+        let diff = PublicApiDiff::between_with_renames(old, new, &renames);
+        assert!(diff.removed.is_empty());
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].old.to_string(),
+            "pub fn krate::new::g(x: i32)"
+        );
+    }
+
+    #[test]
+    fn renamed_in_text_only_renames_whole_paths() {
+        let from = [String::from("krate"), String::from("a")];
+        let to = [String::from("krate"), String::from("b")];
+        assert_eq!(
+            renamed_in_text("impl Send for krate::a::S", &from, &to),
+            "impl Send for krate::b::S"
+        );
+        assert_eq!(
+            renamed_in_text("krate::ab::S, other::krate::a::S, krate::a", &from, &to),
+            "krate::ab::S, other::krate::a::S, krate::b"
+        );
+    }
+
+    #[test]
+    fn github_annotations() {
+        let diff = PublicApiDiff {
+            removed: vec![item_with_path("a")],
+            changed: vec![ChangedPublicItem {
+                old: with_span(item_with_path("b"), "src/old.rs", 1),
+                new: with_span(item_with_path("b:c"), "src/new,1.rs", 7),
+            }],
+            added: vec![
+                in_exhaustive_type(item_with_path("d")),
+                item_with_path("e%"),
+            ],
+            moved: vec![],
+        };
+
+        assert_eq!(
+            to_github_annotations(&diff, &GithubAnnotationLevels::default()),
+            vec![
+                "::error title=public function removed::a",
+                "::error file=src/new%2C1.rs,line=7,title=signature of public function changed::b:c",
+                "::error title=required trait function added::d",
+                "::warning title=public function added::e%25",
+            ]
+        );
+
+        let levels = GithubAnnotationLevels {
+            breaking: Some(AnnotationLevel::Warning),
+            non_breaking: None,
+        };
+        assert_eq!(
+            to_github_annotations(&diff, &levels),
+            vec![
+                "::warning title=public function removed::a",
+                "::warning file=src/new%2C1.rs,line=7,title=signature of public function changed::b:c",
+                "::warning title=required trait function added::d",
+            ]
+        );
+    }
+
+    #[test]
+    fn item_that_became_doc_hidden() {
+        let old = api([item_with_path("a::f")]);
+        let new = api([doc_hidden(item_with_path("a::f"))]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert!(diff.removed.is_empty());
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].category(), ChangeCategory::BecameDocHidden);
+    }
+
+    #[test]
+    fn item_that_is_no_longer_doc_hidden() {
+        let old = api([doc_hidden(item_with_path("a::f"))]);
+        let new = api([item_with_path("a::f")]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].category(),
+            ChangeCategory::NoLongerDocHidden
+        );
+    }
+
+    #[test]
+    fn becoming_doc_hidden_is_not_breaking() {
+        let old = api([item_with_path("a::f")]);
+        let new = api([doc_hidden(item_with_path("a::f"))]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.semver_impact(), SemverImpact::Patch);
+        assert!(!diff.is_breaking());
+        assert!(breaking_changes(&diff).is_empty());
+    }
+
+    #[test]
+    fn no_longer_being_doc_hidden_is_not_breaking() {
+        let old = api([doc_hidden(item_with_path("a::f"))]);
+        let new = api([item_with_path("a::f")]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.semver_impact(), SemverImpact::Patch);
+        assert!(!diff.is_breaking());
+        assert!(breaking_changes(&diff).is_empty());
+    }
+
+    #[test]
+    fn must_use_and_deprecated_changes_are_not_breaking() {
+        let old = api([item_with_path("a::f"), item_with_path("a::g")]);
+        let new = api([
+            with_attribute(item_with_path("a::f"), "#[must_use]"),
+            with_attribute(item_with_path("a::g"), "#[deprecated(note = \"use f\")]"),
+        ]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.changed.len(), 2);
+        for changed in &diff.changed {
+            assert_eq!(
+                changed.category(),
+                ChangeCategory::MustUseOrDeprecationChanged
+            );
+        }
+        assert_eq!(diff.semver_impact(), SemverImpact::Patch);
+        assert!(breaking_changes(&diff).is_empty());
+    }
+
+    #[test]
+    fn other_attribute_changes_are_breaking() {
+        let old = api([item_with_path("a::S")]);
+        let new = api([with_attribute(item_with_path("a::S"), "#[repr(C)]")]);
+
+        let diff = PublicApiDiff::between(old, new);
+        assert_eq!(diff.changed[0].category(), ChangeCategory::Other);
+        assert_eq!(diff.semver_impact(), SemverImpact::Major);
+    }
+
+    #[test]
+    fn item_that_stays_doc_hidden() {
+        let old = api([doc_hidden(item_with_path("a::f"))]);
+        let new = api([doc_hidden(item_with_path("a::f"))]);
+
+        assert!(PublicApiDiff::between(old, new).is_empty());
+    }
+
     fn item_with_path(path_str: &str) -> PublicItem {
         new_public_item(
             path_str
@@ -316,6 +2417,15 @@ mod tests {
         PublicApi {
             items: items.into_iter().collect(),
             missing_item_ids: vec![],
+            target: None,
+            crate_name: None,
+        }
+    }
+
+    fn api_of_crate(name: &str, items: impl IntoIterator<Item = PublicItem>) -> PublicApi {
+        PublicApi {
+            crate_name: Some(String::from(name)),
+            ..api(items)
         }
     }
 
@@ -341,13 +2451,83 @@ mod tests {
         new_public_item(path, tokens)
     }
 
+    /// E.g. "pub fn f<T, U>(x: T)"
+    fn fn_with_generic_params(params: &[&str], param_type: &str) -> PublicItem {
+        let mut tokens = vec![q("pub"), w(), k("fn"), w(), Token::function("f"), s("<")];
+        tokens.extend(
+            itertools::intersperse(
+                params.iter().map(|param| vec![Token::generic(*param)]),
+                vec![s(","), w()],
+            )
+            .flatten(),
+        );
+        tokens.extend(vec![
+            s(">"),
+            s("("),
+            i("x"),
+            s(":"),
+            w(),
+            Token::generic(param_type),
+            s(")"),
+        ]);
+        new_public_item(vec![String::from("f")], tokens)
+    }
+
+    /// E.g. "pub struct krate::old::Foo"
+    fn struct_in_module(modules: &[&str], name: &str) -> PublicItem {
+        let mut path: Vec<_> = modules
+            .iter()
+            .map(|module| formatted_sorting_prefix(MODULE_SORTING_PREFIX) + module)
+            .collect();
+        path.push(format!("009-{name}"));
+
+        let mut tokens = vec![q("pub"), w(), k("struct"), w()];
+        for module in modules {
+            tokens.extend([i(module), s("::")]);
+        }
+        tokens.push(t(name));
+
+        with_kind(new_public_item(path, tokens), ItemKind::Struct)
+    }
+
     fn new_public_item(path: PublicItemPath, tokens: Vec<Token>) -> PublicItem {
         PublicItem {
             sortable_path: path,
             tokens,
+            kind: ItemKind::Function,
+            in_exhaustive_type: false,
+            doc_hidden: false,
+            missing_docs: false,
+            span: None,
         }
     }
 
+    fn in_exhaustive_type(mut item: PublicItem) -> PublicItem {
+        item.in_exhaustive_type = true;
+        item
+    }
+
+    fn doc_hidden(mut item: PublicItem) -> PublicItem {
+        item.doc_hidden = true;
+        item
+    }
+
+    fn with_span(mut item: PublicItem, file: &str, line: usize) -> PublicItem {
+        item.span = Some((std::path::PathBuf::from(file), line));
+        item
+    }
+
+    fn with_attribute(mut item: PublicItem, attr: &str) -> PublicItem {
+        item.tokens
+            .splice(0..0, [Token::Annotation(String::from(attr)), w()]);
+        item
+    }
+
+    fn with_kind(mut item: PublicItem, kind: ItemKind) -> PublicItem {
+        item.kind = kind;
+        item
+    }
+
     fn s(s: &str) -> Token {
         Token::symbol(s)
     }
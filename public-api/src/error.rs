@@ -1,19 +1,137 @@
-use thiserror::Error;
+use std::fmt::Display;
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 #[non_exhaustive]
 /// Enumerates all errors that can currently occur within this crate.
 pub enum Error {
     /// Occurs if the rustdoc JSON you provide can't be parsed. Typically
     /// because the rustdoc JSON format that your version of nightly outputs is
     /// too old. Consult the "Compatibility matrix" in the README.
-    #[error(transparent)]
-    SerdeJsonError(#[from] serde_json::Error),
+    SerdeJsonError(serde_json::Error),
 
     /// Some kind of IO error occurred. For example, we might not have read
     /// permissions on the rustdoc JSON input file.
-    #[error(transparent)]
-    IoError(#[from] std::io::Error),
+    IoError(std::io::Error),
+
+    /// Occurs if you try to diff the public APIs of two differently named
+    /// crates with [`crate::diff::PublicApiDiff::try_between`]. Usually this
+    /// means the wrong rustdoc JSON file was passed as input.
+    CrateNameMismatch {
+        /// Name of the crate of the old public API
+        old: String,
+        /// Name of the crate of the new public API
+        new: String,
+    },
+
+    /// Occurs if a [`crate::diff::ApiIndex`] was created by a different
+    /// version of this library. Items might be rendered differently between
+    /// versions, so diffing against a stale index would give a bogus diff.
+    /// Create a new index to fix this.
+    StaleApiIndex {
+        /// Version of this library that created the index
+        index_version: String,
+        /// Version of this library that is in use
+        current_version: String,
+    },
+
+    /// Occurs if the `format_version` of the rustdoc JSON is not the one that
+    /// this library supports. See [`crate::check_format_version`]. Consult the
+    /// "Compatibility matrix" in the README to find a matching version of
+    /// nightly.
+    UnsupportedFormatVersion {
+        /// The format version of the rustdoc JSON
+        found: u32,
+        /// The format version that this library supports
+        supported: u32,
+    },
+
+    /// Occurs if [`crate::Builder::strict()`] is enabled and the public API
+    /// contains something that this library can't fully represent, so that
+    /// part of the public API would otherwise be missing or only rendered as
+    /// a placeholder.
+    UnsupportedConstruct {
+        /// What can't be represented, e.g. `opaque type`
+        construct: String,
+        /// Where it was encountered, e.g. the rendered item
+        location: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SerdeJsonError(e) => match e.classify() {
+                serde_json::error::Category::Io => {
+                    write!(f, "Failed to parse rustdoc JSON: the input could not be read")
+                }
+                serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
+                    write!(f, "Failed to parse rustdoc JSON: the input is not valid JSON")
+                }
+                // Valid JSON with an unexpected structure is what a rustdoc
+                // JSON format change typically looks like
+                serde_json::error::Category::Data => write!(
+                    f,
+                    "Failed to parse rustdoc JSON: the input has an unexpected structure. This \
+                    usually means it was built with a nightly toolchain whose rustdoc JSON format \
+                    is not supported by this version of public-api. Consult the \"Compatibility \
+                    matrix\" in the README"
+                ),
+            },
+            Self::IoError(e) => Display::fmt(e, f),
+            Self::CrateNameMismatch { old, new } => write!(
+                f,
+                "Can't diff public API of crate `{old}` against crate `{new}`"
+            ),
+            Self::StaleApiIndex {
+                index_version,
+                current_version,
+            } => write!(
+                f,
+                "API index was created with public-api {index_version}, but this is public-api {current_version}"
+            ),
+            Self::UnsupportedFormatVersion { found, supported } => write!(
+                f,
+                "rustdoc JSON format version {found} is not supported, only version {supported} is"
+            ),
+            Self::UnsupportedConstruct {
+                construct,
+                location,
+            } => write!(
+                f,
+                "Can't fully represent {construct} `{location}` (strict mode is enabled)"
+            ),
+        }
+    }
+}
+
+// Written by hand rather than with `thiserror` to keep the dependencies of
+// this library to a minimum
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            // The serde error refers to byte offsets in the JSON, so rather
+            // than showing it we expose it here for programmatic inspection
+            Self::SerdeJsonError(e) => Some(e),
+            // Same as `#[error(transparent)]` would give us
+            Self::IoError(e) => e.source(),
+            Self::CrateNameMismatch { .. }
+            | Self::StaleApiIndex { .. }
+            | Self::UnsupportedFormatVersion { .. }
+            | Self::UnsupportedConstruct { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(source: serde_json::Error) -> Self {
+        Self::SerdeJsonError(source)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(source: std::io::Error) -> Self {
+        Self::IoError(source)
+    }
 }
 
 /// Shorthand for [`std::result::Result<T, public_api::Error>`].
@@ -1,4 +1,4 @@
-use rustdoc_types::Item;
+use rustdoc_types::{Item, ItemEnum, Struct, StructKind, Variant, VariantKind};
 
 use crate::nameable_item::NameableItem;
 use crate::path_component::PathComponent;
@@ -34,6 +34,86 @@ impl<'c> IntermediatePublicItem<'c> {
         &self.path
     }
 
+    /// The item that this item is part of, e.g. the struct of a struct field.
+    #[must_use]
+    pub fn parent(&self) -> Option<&'c Item> {
+        let parent_index = self.path().len().checked_sub(2)?;
+        Some(self.path()[parent_index].item.item)
+    }
+
+    /// Returns `true` if this item is a field or variant of a type that other
+    /// crates can construct or match on exhaustively. Adding such an item is a
+    /// breaking change. This is not the case if the type is
+    /// `#[non_exhaustive]` or if a struct has private fields.
+    ///
+    /// Also returns `true` for required items of traits, i.e. methods without
+    /// a default body and associated types and consts without a default,
+    /// since adding one breaks all implementors of the trait.
+    #[must_use]
+    pub fn in_exhaustive_type(&self) -> bool {
+        let Some(parent) = self.parent() else {
+            return false;
+        };
+        let parent_is_non_exhaustive = parent.attrs.iter().any(|a| a == "#[non_exhaustive]");
+
+        match (&self.item().inner, &parent.inner) {
+            (ItemEnum::StructField(_), ItemEnum::Struct(Struct { kind, .. })) => {
+                !parent_is_non_exhaustive
+                    && match kind {
+                        StructKind::Plain {
+                            fields_stripped, ..
+                        } => !fields_stripped,
+                        // Stripped (i.e. private) fields are `None`
+                        StructKind::Tuple(fields) => fields.iter().all(Option::is_some),
+                        StructKind::Unit => false,
+                    }
+            }
+            (ItemEnum::StructField(_), ItemEnum::Variant(Variant { kind, .. })) => {
+                !parent_is_non_exhaustive
+                    && match kind {
+                        VariantKind::Struct {
+                            fields_stripped, ..
+                        } => !fields_stripped,
+                        // Stripped (i.e. private) fields are `None`
+                        VariantKind::Tuple(fields) => fields.iter().all(Option::is_some),
+                        VariantKind::Plain => false,
+                    }
+            }
+            (ItemEnum::Variant(_), ItemEnum::Enum(_)) => !parent_is_non_exhaustive,
+            (ItemEnum::Function(function), ItemEnum::Trait(_)) => !function.has_body,
+            (
+                ItemEnum::AssocType { default: None, .. }
+                | ItemEnum::AssocConst { default: None, .. },
+                ItemEnum::Trait(_),
+            ) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the item itself is marked `#[doc(hidden)]`. Such items
+    /// are only present in rustdoc JSON built with `--document-hidden-items`.
+    #[must_use]
+    pub fn doc_hidden(&self) -> bool {
+        self.item().attrs.iter().any(|a| a == "#[doc(hidden)]")
+    }
+
+    /// Returns `true` if the item has no docs even though it should have, with
+    /// the same exceptions as the `missing_docs` lint: impls and the items of
+    /// trait impls do not need docs, since the trait documents them.
+    #[must_use]
+    pub fn missing_docs(&self) -> bool {
+        let in_trait_impl = self.path().iter().any(|component| {
+            matches!(&component.item.item.inner, ItemEnum::Impl(impl_) if impl_.trait_.is_some())
+        });
+        let has_docs = self
+            .item()
+            .docs
+            .as_ref()
+            .is_some_and(|docs| !docs.trim().is_empty());
+
+        !has_docs && !in_trait_impl && !matches!(self.item().inner, ItemEnum::Impl(_))
+    }
+
     /// See [`crate::item_processor::sorting_prefix()`] docs for an explanation why we have this.
     #[must_use]
     pub fn sortable_path(&self, context: &RenderingContext) -> PublicItemPath {
@@ -0,0 +1,141 @@
+use rustdoc_types::{Item, ItemEnum};
+use serde::{Deserialize, Serialize};
+
+/// What kind of Rust item a [`crate::PublicItem`] is. Useful for e.g. summaries
+/// such as "2 structs removed, 1 function added".
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[non_exhaustive] // More kinds might be distinguished in the future
+pub enum ItemKind {
+    /// `extern crate foo`
+    ExternCrate,
+
+    /// `use foo::bar`. Only used when the imported item can't be inlined.
+    Import,
+
+    /// A primitive type such as `i32`
+    Primitive,
+
+    /// `mod foo`
+    Module,
+
+    /// `macro_rules! foo`
+    Macro,
+
+    /// A procedural macro, e.g. `#[derive(Foo)]`
+    ProcMacro,
+
+    /// `enum Foo`
+    Enum,
+
+    /// `union Foo`
+    Union,
+
+    /// `struct Foo`
+    Struct,
+
+    /// A field of a struct, union or enum variant
+    StructField,
+
+    /// A variant of an enum
+    Variant,
+
+    /// `const FOO: usize`
+    Constant,
+
+    /// `static FOO: usize`
+    Static,
+
+    /// `trait Foo`
+    Trait,
+
+    /// `type Foo` inside a trait or impl
+    AssocType,
+
+    /// `const FOO: usize` inside a trait or impl
+    AssocConst,
+
+    /// `fn foo()`, including associated functions without a `self` receiver
+    /// such as `fn new() -> Self`
+    Function,
+
+    /// `fn foo(&self)`, i.e. a function with a `self` receiver
+    Method,
+
+    /// `type Foo = Bar`
+    TypeAlias,
+
+    /// `impl Foo` or `impl Trait for Foo`
+    Impl,
+
+    /// `extern { type Foo; }`
+    ForeignType,
+
+    /// `type Foo = impl Trait`
+    OpaqueTy,
+
+    /// `trait Foo = Bar`
+    TraitAlias,
+}
+
+impl ItemKind {
+    /// A short description of the kind in plain words, e.g. `struct field`,
+    /// for use in messages.
+    #[cfg(feature = "diff")]
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Self::ExternCrate => "extern crate",
+            Self::Import => "import",
+            Self::Primitive => "primitive type",
+            Self::Module => "module",
+            Self::Macro => "macro",
+            Self::ProcMacro => "proc macro",
+            Self::Enum => "enum",
+            Self::Union => "union",
+            Self::Struct => "struct",
+            Self::StructField => "field",
+            Self::Variant => "enum variant",
+            Self::Constant => "constant",
+            Self::Static => "static",
+            Self::Trait => "trait",
+            Self::AssocType => "associated type",
+            Self::AssocConst => "associated constant",
+            Self::Function => "function",
+            Self::Method => "method",
+            Self::TypeAlias => "type alias",
+            Self::Impl => "impl",
+            Self::ForeignType => "foreign type",
+            Self::OpaqueTy => "opaque type",
+            Self::TraitAlias => "trait alias",
+        }
+    }
+
+    pub(crate) fn from_item(item: &Item) -> Self {
+        match &item.inner {
+            ItemEnum::ExternCrate { .. } => Self::ExternCrate,
+            ItemEnum::Import(_) => Self::Import,
+            ItemEnum::Primitive(_) => Self::Primitive,
+            ItemEnum::Module(_) => Self::Module,
+            ItemEnum::Macro(_) => Self::Macro,
+            ItemEnum::ProcMacro(_) => Self::ProcMacro,
+            ItemEnum::Enum(_) => Self::Enum,
+            ItemEnum::Union(_) => Self::Union,
+            ItemEnum::Struct(_) => Self::Struct,
+            ItemEnum::StructField(_) => Self::StructField,
+            ItemEnum::Variant(_) => Self::Variant,
+            ItemEnum::Constant(_) => Self::Constant,
+            ItemEnum::Static(_) => Self::Static,
+            ItemEnum::Trait(_) => Self::Trait,
+            ItemEnum::AssocType { .. } => Self::AssocType,
+            ItemEnum::AssocConst { .. } => Self::AssocConst,
+            ItemEnum::Function(function) if matches!(function.decl.inputs.first(), Some((name, _)) if name == "self") => {
+                Self::Method
+            }
+            ItemEnum::Function(_) => Self::Function,
+            ItemEnum::TypeAlias(_) => Self::TypeAlias,
+            ItemEnum::Impl(_) => Self::Impl,
+            ItemEnum::ForeignType => Self::ForeignType,
+            ItemEnum::OpaqueTy(_) => Self::OpaqueTy,
+            ItemEnum::TraitAlias(_) => Self::TraitAlias,
+        }
+    }
+}
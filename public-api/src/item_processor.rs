@@ -2,16 +2,21 @@ use super::nameable_item::NameableItem;
 use crate::{
     crate_wrapper::CrateWrapper, intermediate_public_item::IntermediatePublicItem,
     path_component::PathComponent, public_item::PublicItem, render::RenderingContext,
-    BuilderOptions as Options, PublicApi,
+    BlanketImplPolicy, BuilderOptions as Options, Error, PublicApi, Result, SortMode,
 };
 use rustdoc_types::{
     Crate, Id, Impl, Import, Item, ItemEnum, Module, Struct, StructKind, Type, VariantKind,
 };
 use std::{
+    cell::RefCell,
     collections::{HashMap, VecDeque},
     vec,
 };
 
+/// The `crate_id` that rustdoc JSON uses for items of the documented crate
+/// itself. Items of other crates have other ids.
+const LOCAL_CRATE_ID: u32 = 0;
+
 /// Items in rustdoc JSON reference each other by Id. The [`ItemProcessor`]
 /// essentially takes one Id at a time and figure out what to do with it. Once
 /// complete, the item is ready to be listed as part of the public API, and
@@ -173,13 +178,31 @@ impl<'c> ItemProcessor<'c> {
         item: &'c Item,
         impl_: &'c Impl,
     ) {
-        if !ImplKind::from(item, impl_).is_active(self.options) {
+        if !ImplKind::from(item, impl_).is_active(item, &self.options)
+            || self.is_impl_of_hidden_trait(impl_)
+        {
             return;
         }
 
         self.process_item_for_type(unprocessed_item, item, None, Some(&impl_.for_));
     }
 
+    /// If `impl_` implements one of [`Options::hidden_impl_traits`]. The trait
+    /// can be given either by name, e.g. `Debug`, or by its full path, e.g.
+    /// `core::fmt::Debug`.
+    fn is_impl_of_hidden_trait(&self, impl_: &Impl) -> bool {
+        let Some(trait_) = &impl_.trait_ else {
+            return false;
+        };
+        let name = trait_.name.rsplit("::").next().unwrap_or(&trait_.name);
+        let full_path = self.crate_.path_of(&trait_.id).map(|path| path.join("::"));
+
+        self.options
+            .hidden_impl_traits
+            .iter()
+            .any(|hidden| hidden == name || Some(hidden) == full_path.as_ref())
+    }
+
     /// Make sure the item we are about to process is not already part of the
     /// item path. If it is, we have encountered recursion. Stop processing in
     /// that case.
@@ -313,6 +336,9 @@ impl<'c> UnprocessedItem<'c> {
     }
 }
 
+/// The [`sorting_prefix()`] of modules.
+pub(crate) const MODULE_SORTING_PREFIX: u8 = 4;
+
 /// In order for items in the output to be nicely grouped, we add a prefix to
 /// each item in the path to an item. That way, sorting on the name (with this
 /// prefix) will group items. But we don't want this prefix to be be visible to
@@ -324,7 +350,7 @@ pub(crate) fn sorting_prefix(item: &Item) -> u8 {
 
         ItemEnum::Primitive(_) => 3,
 
-        ItemEnum::Module(_) => 4,
+        ItemEnum::Module(_) => MODULE_SORTING_PREFIX,
 
         ItemEnum::Macro(_) => 5,
         ItemEnum::ProcMacro(_) => 6,
@@ -364,6 +390,14 @@ pub(crate) fn sorting_prefix(item: &Item) -> u8 {
     }
 }
 
+/// Formats a [`sorting_prefix()`] the way it is prepended to each component of
+/// [`crate::PublicItem::sortable_path`], e.g. `004-` for modules.
+pub(crate) fn formatted_sorting_prefix(sorting_prefix: u8) -> String {
+    // Note that in order for the prefix to sort properly lexicographically,
+    // we need to pad it with leading zeroes.
+    format!("{sorting_prefix:0>3}-")
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum ImplKind {
     /// E.g. `impl Foo` or `impl<'a> Foo<'a>`
@@ -402,9 +436,13 @@ impl ImplKind {
 }
 
 impl ImplKind {
-    fn is_active(&self, options: Options) -> bool {
+    fn is_active(&self, impl_item: &Item, options: &Options) -> bool {
         match self {
-            ImplKind::Blanket => !options.omit_blanket_impls,
+            ImplKind::Blanket => match options.blanket_impl_policy {
+                BlanketImplPolicy::All => true,
+                BlanketImplPolicy::LocalOnly => impl_item.crate_id == LOCAL_CRATE_ID,
+                BlanketImplPolicy::None => false,
+            },
             ImplKind::AutoTrait => !options.omit_auto_trait_impls,
             ImplKind::AutoDerived => !options.omit_auto_derived_impls,
             ImplKind::Inherent | ImplKind::Trait => true,
@@ -428,6 +466,8 @@ const fn children_for_item(item: &Item) -> Option<&Vec<Id>> {
         }) => Some(fields),
         ItemEnum::Enum(e) => Some(&e.variants),
         ItemEnum::Trait(t) => Some(&t.items),
+        // Only items defined by the impl itself. Inherited default methods are
+        // in `provided_trait_methods`, which we deliberately ignore
         ItemEnum::Impl(i) => Some(&i.items),
         _ => None,
     }
@@ -444,23 +484,97 @@ pub fn impls_for_item(item: &Item) -> Option<&[Id]> {
     }
 }
 
-pub(crate) fn public_api_in_crate(crate_: &Crate, options: Options) -> super::PublicApi {
-    let mut item_processor = ItemProcessor::new(crate_, options);
+pub(crate) fn public_api_in_crate(
+    crate_: &Crate,
+    options: Options,
+    mut visitor: impl FnMut(&PublicItem, &Item),
+) -> Result<PublicApi> {
+    let sort_mode = options.sort_mode;
+    let mut items = vec![];
+    let missing_item_ids = for_each_public_item_in_crate(crate_, options, |item, rustdoc_item| {
+        visitor(&item, rustdoc_item);
+        let source_location = rustdoc_item
+            .span
+            .as_ref()
+            .map(|span| (span.filename.clone(), span.begin));
+        items.push((source_location, item));
+    })?;
+
+    if sort_mode == SortMode::SourceOrder {
+        // Stable sort, so items declared at the same place (and items without
+        // a span) stay in traversal order. `None` sorts last thanks to `is_none()`
+        items.sort_by(|(a, _), (b, _)| (a.is_none(), a).cmp(&(b.is_none(), b)));
+    }
+
+    Ok(PublicApi {
+        items: items.into_iter().map(|(_, item)| item).collect(),
+        missing_item_ids,
+        target: None,
+        crate_name: crate_
+            .index
+            .get(&crate_.root)
+            .and_then(|root| root.name.clone()),
+    })
+}
+
+/// Like [`public_api_in_crate`], but instead of collecting the items, each
+/// [`PublicItem`] is handed over to `item_fn` as soon as it has been rendered,
+/// together with the rustdoc JSON [`Item`] it was rendered from. Returns the
+/// missing item ids, see [`PublicApi::missing_item_ids`].
+///
+/// With [`Options::strict`], missing item ids and items of
+/// [unsupported kinds](unsupported_item_kind) are errors instead.
+pub(crate) fn for_each_public_item_in_crate(
+    crate_: &Crate,
+    options: Options,
+    mut item_fn: impl FnMut(PublicItem, &Item),
+) -> Result<Vec<String>> {
+    let strict = options.strict;
+    let mut item_processor = ItemProcessor::new(crate_, options.clone());
     item_processor.add_to_work_queue(vec![], &crate_.root);
     item_processor.run();
 
+    let missing_item_ids = item_processor.crate_.missing_item_ids();
+    if let (true, Some(id)) = (strict, missing_item_ids.first()) {
+        return Err(Error::UnsupportedConstruct {
+            construct: String::from("missing item with ID"),
+            location: id.clone(),
+        });
+    }
+
     let context = RenderingContext {
         crate_,
         id_to_items: item_processor.id_to_items(),
         options,
+        expanding_type_aliases: RefCell::default(),
     };
 
-    PublicApi {
-        items: item_processor
-            .output
-            .iter()
-            .map(|item| PublicItem::from_intermediate_public_item(&context, item))
-            .collect::<Vec<_>>(),
-        missing_item_ids: item_processor.crate_.missing_item_ids(),
+    for item in &item_processor.output {
+        let public_item = PublicItem::from_intermediate_public_item(&context, item);
+        if let (true, Some(kind)) = (strict, unsupported_item_kind(item.item())) {
+            return Err(Error::UnsupportedConstruct {
+                construct: String::from(kind),
+                location: public_item.to_string(),
+            });
+        }
+        #[cfg(feature = "regex")]
+        if let Some(line_filter) = &context.options.line_filter {
+            if !line_filter.is_match(&public_item.to_string()) {
+                continue;
+            }
+        }
+        item_fn(public_item, item.item());
+    }
+
+    Ok(missing_item_ids)
+}
+
+/// If `item` is of a kind that we can only render as a placeholder, the name
+/// of that kind. For example, an opaque type is rendered as `opaque type`
+/// followed by its path, without its bounds.
+fn unsupported_item_kind(item: &Item) -> Option<&'static str> {
+    match item.inner {
+        ItemEnum::OpaqueTy(_) => Some("opaque type"),
+        _ => None,
     }
 }
@@ -34,26 +34,51 @@
 // deny in CI, only warn here
 #![warn(clippy::all, missing_docs)]
 
+mod blanket_impl_policy;
+mod bound_style;
+mod canonical;
 mod crate_wrapper;
 mod error;
 mod intermediate_public_item;
+mod item_kind;
 mod item_processor;
 mod nameable_item;
 mod path_component;
+mod path_style;
 mod public_item;
 mod render;
+mod sort_mode;
 pub mod tokens;
 
+#[cfg(feature = "diff")]
 pub mod diff;
 
 use std::path::PathBuf;
 
+// Documented at the definition site so cargo doc picks it up
+pub use blanket_impl_policy::BlanketImplPolicy;
+
+// Documented at the definition site so cargo doc picks it up
+pub use bound_style::BoundStyle;
+
 // Documented at the definition site so cargo doc picks it up
 pub use error::{Error, Result};
 
+// Documented at the definition site so cargo doc picks it up
+pub use sort_mode::SortMode;
+
+// Documented at the definition site so cargo doc picks it up
+pub use item_kind::ItemKind;
+
+// Documented at the definition site so cargo doc picks it up
+pub use path_style::PathStyle;
+
 // Documented at the definition site so cargo doc picks it up
 pub use public_item::PublicItem;
 
+// Documented at the definition site so cargo doc picks it up
+pub use render::render_item;
+
 /// This constant defines the minimum version of nightly that is required in
 /// order for the rustdoc JSON output to be parsable by this library. Note that
 /// this library is implemented with stable Rust. But the rustdoc JSON that this
@@ -64,14 +89,225 @@ pub use public_item::PublicItem;
 /// nightly or later, you should be fine.
 pub const MINIMUM_NIGHTLY_RUST_VERSION: &str = "nightly-2023-08-25";
 
+/// Checks that the `format_version` of `rustdoc_json_str` is the one that this
+/// library supports, without parsing the rest of the rustdoc JSON into a
+/// [`rustdoc_types::Crate`]. Allows tools to fail fast with a helpful error
+/// message before they build a [`PublicApi`]. Returns the format version.
+///
+/// # Errors
+///
+/// [`Error::UnsupportedFormatVersion`] if the format version is not supported,
+/// and [`Error::SerdeJsonError`] if the JSON can't be parsed or has no
+/// `format_version`.
+pub fn check_format_version(rustdoc_json_str: &str) -> Result<u32> {
+    #[derive(serde::Deserialize)]
+    struct FormatVersionOnly {
+        format_version: u32,
+    }
+
+    let FormatVersionOnly { format_version } =
+        deserialize_without_recursion_limit(rustdoc_json_str)?;
+    if format_version == rustdoc_types::FORMAT_VERSION {
+        Ok(format_version)
+    } else {
+        Err(Error::UnsupportedFormatVersion {
+            found: format_version,
+            supported: rustdoc_types::FORMAT_VERSION,
+        })
+    }
+}
+
+/// The paths of all modules in the public API, e.g. `["krate", "sub"]` for
+/// `krate::sub`. Both modules that are items in `items` and modules that
+/// items are in are included, so parent modules are included even if they
+/// are not in `items` themselves. Sorted, and without duplicates.
+#[must_use]
+pub fn modules(items: &[PublicItem]) -> Vec<Vec<String>> {
+    let module_prefix =
+        item_processor::formatted_sorting_prefix(item_processor::MODULE_SORTING_PREFIX);
+
+    let mut modules = std::collections::BTreeSet::new();
+    for item in items {
+        let path = item
+            .sortable_path
+            .iter()
+            .map_while(|component| component.strip_prefix(&module_prefix))
+            .map(String::from)
+            .collect::<Vec<_>>();
+        for len in 1..=path.len() {
+            modules.insert(path[..len].to_vec());
+        }
+    }
+    modules.into_iter().collect()
+}
+
+/// The items in `items` that lack documentation, e.g. to fail CI when a new
+/// public item is undocumented. Items with only whitespace in their docs
+/// count as undocumented. Like with the `missing_docs` lint, impls and the
+/// items of trait impls are never included, since they are rarely documented.
+/// Items created with [`PublicItem::from_str_parts()`] are never included
+/// either, since they carry no docs information.
+#[must_use]
+pub fn undocumented(items: &[PublicItem]) -> Vec<&PublicItem> {
+    items.iter().filter(|item| item.missing_docs).collect()
+}
+
+/// The paths of all non-primitive types that appear in `items`, e.g.
+/// `serde_json::Value` or `::globset::Error`, as rendered. Use this to find out
+/// what types of dependencies the public API exposes, by filtering out paths
+/// that start with the name of the crate itself. Types declared by the crate
+/// are included too, since their names also appear in the items. Sorted, and
+/// without duplicates.
+#[must_use]
+pub fn referenced_types(items: &[PublicItem]) -> std::collections::BTreeSet<String> {
+    let mut types = std::collections::BTreeSet::new();
+    for item in items {
+        // The path of a type is rendered as identifiers separated by `::`
+        // followed by the type itself, so collect the identifiers until we
+        // find out if they lead up to a type. A leading `::` is rendered as
+        // an empty identifier, so `None` and `Some("")` are different here
+        let mut path: Option<String> = None;
+        for token in &item.tokens {
+            match token {
+                tokens::Token::Identifier(name) => {
+                    path.get_or_insert_with(String::new).push_str(name);
+                }
+                tokens::Token::Symbol(symbol) if symbol == "::" => {
+                    if let Some(path) = &mut path {
+                        path.push_str("::");
+                    }
+                }
+                tokens::Token::Type(name) => {
+                    let mut path = path.take().unwrap_or_default();
+                    path.push_str(name);
+                    types.insert(path);
+                }
+                _ => path = None,
+            }
+        }
+    }
+    types
+}
+
+/// A fingerprint of a public API, for a cheap "did anything change at all"
+/// check, e.g. in CI. Store the fingerprint of a baseline and only do a full
+/// diff if the fingerprint of the current public API differs.
+///
+/// The fingerprint is computed from the rendered items, sorted first so that
+/// it does not depend on the order of `items`. It is stable across runs and
+/// platforms, but items might render differently between versions of this
+/// library, which then changes the fingerprint.
+#[must_use]
+pub fn fingerprint(items: &[PublicItem]) -> u64 {
+    let mut lines = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+    lines.sort();
+    fnv1a(&lines)
+}
+
+/// Like [`fingerprint()`], but items that are [semantically
+/// equal](semantically_equal()) get the same hash, so that e.g. only
+/// reordering the bounds of a generic parameter does not change it. Returned
+/// as 16 hexadecimal digits.
+#[must_use]
+pub fn canonical_hash(items: &[PublicItem]) -> String {
+    format!("{:016x}", fnv1a(&canonical_lines(items)))
+}
+
+/// Whether `a` and `b` are the same public API, disregarding differences that
+/// do not matter to users of it. This is more robust than comparing the
+/// rendered items, which e.g. changes whenever the rendering of this library is
+/// tweaked. Exactly the following is normalized:
+///
+/// * The order of the items.
+/// * Whitespace. Only whitespace needed to keep words apart is kept.
+/// * The order of bounds, e.g. `T: Send + Clone` and `T: Clone + Send` are
+///   equal. This applies to all `+`-separated lists of bounds, including
+///   those of `impl Trait`, `dyn Trait`, where clauses and supertraits.
+/// * The names of lifetimes, which are renamed in order of first appearance
+///   within each item, e.g. `fn f<'a>(x: &'a u8)` and `fn f<'b>(x: &'b u8)`
+///   are equal. `'static` and `'_` are not renamed.
+///
+/// Everything else, such as the order of fields, variants and generic
+/// parameters, matters.
+#[must_use]
+pub fn semantically_equal(a: &[PublicItem], b: &[PublicItem]) -> bool {
+    canonical_lines(a) == canonical_lines(b)
+}
+
+/// The sorted canonical text of each item, see [`semantically_equal()`].
+fn canonical_lines(items: &[PublicItem]) -> Vec<String> {
+    let mut lines = items
+        .iter()
+        .map(canonical::canonical_string)
+        .collect::<Vec<_>>();
+    lines.sort();
+    lines
+}
+
+/// 64-bit FNV-1a of `lines`, each followed by a newline. We can't use
+/// `std::hash::DefaultHasher` since its algorithm is not guaranteed to stay
+/// the same between Rust releases.
+fn fnv1a(lines: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in lines.iter().flat_map(|line| line.bytes().chain([b'\n'])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
 /// See [`Builder`] method docs for what each field means.
-#[derive(Copy, Clone, Debug)]
+#[derive(Clone, Debug)]
 struct BuilderOptions {
-    sorted: bool,
+    sort_mode: SortMode,
     debug_sorting: bool,
-    omit_blanket_impls: bool,
+    blanket_impl_policy: BlanketImplPolicy,
     omit_auto_trait_impls: bool,
     omit_auto_derived_impls: bool,
+    omit_visibility: bool,
+    include_doc_summary: bool,
+    include_behavior_attrs: bool,
+    include_diagnostic_attrs: bool,
+    include_spans: bool,
+    enum_generics_in_paths: bool,
+    struct_generics_in_paths: bool,
+    path_style: PathStyle,
+    bound_style: BoundStyle,
+    resolve_type_aliases: bool,
+    qualified_trait_impl_paths: bool,
+    hide_elided_lifetimes: bool,
+    hidden_impl_traits: Vec<String>,
+    #[cfg(feature = "regex")]
+    line_filter: Option<regex::Regex>,
+    strict: bool,
+}
+
+impl Default for BuilderOptions {
+    fn default() -> Self {
+        Self {
+            sort_mode: SortMode::Sorted,
+            debug_sorting: false,
+            blanket_impl_policy: BlanketImplPolicy::All,
+            omit_auto_trait_impls: false,
+            omit_auto_derived_impls: false,
+            omit_visibility: false,
+            include_doc_summary: false,
+            include_behavior_attrs: false,
+            include_diagnostic_attrs: false,
+            include_spans: false,
+            enum_generics_in_paths: false,
+            struct_generics_in_paths: false,
+            path_style: PathStyle::Full,
+            bound_style: BoundStyle::AsWritten,
+            resolve_type_aliases: false,
+            qualified_trait_impl_paths: false,
+            hide_elided_lifetimes: false,
+            hidden_impl_traits: vec![],
+            #[cfg(feature = "regex")]
+            line_filter: None,
+            strict: false,
+        }
+    }
 }
 
 /// Builds [`PublicApi`]s. See the [top level][`crate`] module docs for example
@@ -80,6 +316,7 @@ struct BuilderOptions {
 pub struct Builder {
     rustdoc_json: PathBuf,
     options: BuilderOptions,
+    target: Option<String>,
 }
 
 impl Builder {
@@ -87,16 +324,10 @@ impl Builder {
     /// [top level][`crate`] module docs for example code.
     #[must_use]
     pub fn from_rustdoc_json(path: impl Into<PathBuf>) -> Self {
-        let options = BuilderOptions {
-            sorted: true,
-            debug_sorting: false,
-            omit_blanket_impls: false,
-            omit_auto_trait_impls: false,
-            omit_auto_derived_impls: false,
-        };
         Self {
             rustdoc_json: path.into(),
-            options,
+            options: BuilderOptions::default(),
+            target: None,
         }
     }
 
@@ -107,9 +338,24 @@ impl Builder {
     ///
     /// The default value is `true`, because usually the performance impact is
     /// negligible, and is is generally more practical to work with sorted data.
+    /// Use [`Self::sort_mode()`] to list items in source code order instead.
     #[must_use]
-    pub fn sorted(mut self, sorted: bool) -> Self {
-        self.options.sorted = sorted;
+    pub fn sorted(self, sorted: bool) -> Self {
+        self.sort_mode(if sorted {
+            SortMode::Sorted
+        } else {
+            SortMode::Unsorted
+        })
+    }
+
+    /// In what order to list items. With [`SortMode::SourceOrder`], the
+    /// listing mirrors the layout of the source files of the crate. Overrides
+    /// [`Self::sorted()`] and vice versa.
+    ///
+    /// The default value is [`SortMode::Sorted`].
+    #[must_use]
+    pub fn sort_mode(mut self, sort_mode: SortMode) -> Self {
+        self.options.sort_mode = sort_mode;
         self
     }
 
@@ -131,10 +377,28 @@ impl Builder {
     /// Borrow<T> for T`, and `impl<T, U> Into<U> for T where U: From<T>`
     ///
     /// The default value is `false` so that the listed public API is complete
-    /// by default.
+    /// by default. Use [`Self::blanket_impl_policy()`] to only omit Blanket
+    /// Implementations of other crates.
     #[must_use]
-    pub fn omit_blanket_impls(mut self, omit_blanket_impls: bool) -> Self {
-        self.options.omit_blanket_impls = omit_blanket_impls;
+    pub fn omit_blanket_impls(self, omit_blanket_impls: bool) -> Self {
+        self.blanket_impl_policy(if omit_blanket_impls {
+            BlanketImplPolicy::None
+        } else {
+            BlanketImplPolicy::All
+        })
+    }
+
+    /// Which items that belong to Blanket Implementations to include. With
+    /// [`BlanketImplPolicy::LocalOnly`], blanket impls of the crate itself,
+    /// such as `impl<T> MyExt for T`, are kept while those of other crates,
+    /// such as `impl<T> Any for T`, are omitted. Overrides
+    /// [`Self::omit_blanket_impls()`] and vice versa.
+    ///
+    /// The default value is [`BlanketImplPolicy::All`] so that the listed
+    /// public API is complete by default.
+    #[must_use]
+    pub fn blanket_impl_policy(mut self, blanket_impl_policy: BlanketImplPolicy) -> Self {
+        self.options.blanket_impl_policy = blanket_impl_policy;
         self
     }
 
@@ -166,15 +430,292 @@ impl Builder {
         self
     }
 
+    /// If `true`, the leading `pub ` is left out when rendering items. Useful
+    /// if you want to post-process the output into a more compact format of
+    /// your own.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn omit_visibility(mut self, omit_visibility: bool) -> Self {
+        self.options.omit_visibility = omit_visibility;
+        self
+    }
+
+    /// If `true`, the first line of the doc comment of an item is appended to
+    /// the rendered item, like this: `pub fn foo() // Does foo`. Items without
+    /// docs are rendered as usual. Note that this makes diffs report an item as
+    /// changed if only its docs changed.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn include_doc_summary(mut self, include_doc_summary: bool) -> Self {
+        self.options.include_doc_summary = include_doc_summary;
+        self
+    }
+
+    /// If `true`, attributes that affect how an item behaves for callers are
+    /// rendered in addition to the ones that are always rendered, like this:
+    /// `#[track_caller] pub fn foo()`. The list is kept short on purpose and
+    /// is currently `#[track_caller]`, `#[cold]` and `#[target_feature]`.
+    /// Attributes such as `#[inline]` are never rendered since they are
+    /// implementation details.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn include_behavior_attrs(mut self, include_behavior_attrs: bool) -> Self {
+        self.options.include_behavior_attrs = include_behavior_attrs;
+        self
+    }
+
+    /// If `true`, `#[diagnostic::on_unimplemented]` attributes on traits are
+    /// rendered, like this: `#[diagnostic::on_unimplemented(message = "...")]
+    /// pub trait Foo`. The message is part of the experience of users of the
+    /// trait, so some library authors want changes to it tracked. Other tool
+    /// attributes are not rendered.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn include_diagnostic_attrs(mut self, include_diagnostic_attrs: bool) -> Self {
+        self.options.include_diagnostic_attrs = include_diagnostic_attrs;
+        self
+    }
+
+    /// If `true`, each item records where in the source code it is defined,
+    /// see [`PublicItem::span()`]. Useful to point at the source of changed
+    /// items, e.g. with [`diff::to_github_annotations()`]. Spans are not part
+    /// of the rendered items, so they do not affect listing or diffing.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn include_spans(mut self, include_spans: bool) -> Self {
+        self.options.include_spans = include_spans;
+        self
+    }
+
+    /// If `true`, the generic parameters of an enum are included in the paths
+    /// of its variants and their fields, like this: `pub Option<T>::Some(T)`.
+    /// Without that context, it is not obvious where e.g. the `T` in the tuple
+    /// of a variant comes from.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn enum_generics_in_paths(mut self, enum_generics_in_paths: bool) -> Self {
+        self.options.enum_generics_in_paths = enum_generics_in_paths;
+        self
+    }
+
+    /// If `true`, the generic parameters of a struct or union are included in
+    /// the paths of its fields, like this: `pub Foo<'a, T>::bar: &'a T`. Like
+    /// [`Self::enum_generics_in_paths()`], but for fields of structs and
+    /// unions. Paths of items of impls are not affected.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn struct_generics_in_paths(mut self, struct_generics_in_paths: bool) -> Self {
+        self.options.struct_generics_in_paths = struct_generics_in_paths;
+        self
+    }
+
+    /// How much of the path of each item to render, e.g. `pub fn foo()`
+    /// instead of `pub fn my_crate::module::foo()`. See [`PathStyle`] for the
+    /// available styles. Paths in types that items refer to are not affected.
+    ///
+    /// The default value is [`PathStyle::Full`].
+    #[must_use]
+    pub fn path_style(mut self, path_style: PathStyle) -> Self {
+        self.options.path_style = path_style;
+        self
+    }
+
+    /// Where to render the bounds of generic type parameters, e.g. `fn f<T>()
+    /// where T: Clone` instead of `fn f<T: Clone>()`. Normalizing bounds
+    /// avoids diffs that only come from moving a bound between the parameter
+    /// list and the where clause. See [`BoundStyle`] for the available styles.
+    ///
+    /// The default value is [`BoundStyle::AsWritten`].
+    #[must_use]
+    pub fn bound_style(mut self, bound_style: BoundStyle) -> Self {
+        self.options.bound_style = bound_style;
+        self
+    }
+
+    /// If `true`, type aliases of the crate are replaced with the types they
+    /// alias wherever they are used, e.g. `pub fn read() -> Vec<u8>` instead
+    /// of `pub fn read() -> Bytes` given `pub type Bytes = Vec<u8>`. Generic
+    /// type aliases and type aliases of other crates are not replaced. The type
+    /// alias items themselves are still listed.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn resolve_type_aliases(mut self, resolve_type_aliases: bool) -> Self {
+        self.options.resolve_type_aliases = resolve_type_aliases;
+        self
+    }
+
+    /// If `true`, items of trait impls are rendered with a fully qualified
+    /// path that includes the trait, like this: `pub fn <Foo as
+    /// Trait>::bar(&self)` instead of `pub fn Foo::bar(&self)`. That way an
+    /// inherent method can be told apart from a trait method with the same
+    /// name. Items of inherent impls are rendered as usual.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn qualified_trait_impl_paths(mut self, qualified_trait_impl_paths: bool) -> Self {
+        self.options.qualified_trait_impl_paths = qualified_trait_impl_paths;
+        self
+    }
+
+    /// If `true`, the elided lifetime `'_` is left out of references and
+    /// generic args, e.g. `&'_ T` is rendered as `&T` and `Foo<'_>` as `Foo`.
+    /// This reduces churn when rustdoc changes how it represents elided
+    /// lifetimes. Named lifetimes and `'_` in other places, such as in `dyn
+    /// Trait + '_`, are still rendered since leaving them out can change what
+    /// they mean.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn hide_elided_lifetimes(mut self, hide_elided_lifetimes: bool) -> Self {
+        self.options.hide_elided_lifetimes = hide_elided_lifetimes;
+        self
+    }
+
+    /// Impls of the given traits, and the items of such impls, are omitted
+    /// from the output. A trait can be given by name, e.g. `Debug`, or by its
+    /// full path, e.g. `core::fmt::Debug`. Unlike
+    /// [`Self::omit_auto_derived_impls()`], this applies to all impls of the
+    /// traits, derived or not. For example, with `["Debug", "Clone"]`, a
+    /// `Display` impl is still listed while `Debug` and `Clone` impls are not.
+    ///
+    /// The default value is empty, i.e. no impls are omitted.
+    #[must_use]
+    pub fn hidden_impl_traits(
+        mut self,
+        hidden_impl_traits: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.options.hidden_impl_traits = hidden_impl_traits.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only items whose rendered line matches `line_filter` are included, e.g.
+    /// `^pub fn ` to only include functions. The regex is matched against the
+    /// same string that the [`Display`](std::fmt::Display) impl of
+    /// [`PublicItem`] gives, i.e. the line that is printed when listing the
+    /// public API. Since the filter is applied before a [`PublicApi`] is
+    /// created, diffs of filtered [`PublicApi`]s only contain matching items.
+    ///
+    /// The default value is `None`, i.e. all items are included.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn line_filter(mut self, line_filter: Option<regex::Regex>) -> Self {
+        self.options.line_filter = line_filter;
+        self
+    }
+
+    /// If `true`, building fails with [`Error::UnsupportedConstruct`] as soon
+    /// as something is encountered that can't be fully represented, instead
+    /// of rendering a placeholder or leaving it out. That includes items that
+    /// are referenced but missing from the rustdoc JSON, which otherwise only
+    /// show up in [`PublicApi::missing_item_ids()`]. Use this when auditing a
+    /// public API, to make sure no part of it is silently missed.
+    ///
+    /// The default value is `false`.
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options.strict = strict;
+        self
+    }
+
+    /// The target triple that the rustdoc JSON was built for, e.g.
+    /// `x86_64-unknown-linux-gnu`. Rustdoc JSON does not record this itself, so
+    /// it is only kept as metadata, see [`PublicApi::target()`]. Useful when
+    /// comparing the public API of different targets with
+    /// [`diff::diff_targets`].
+    ///
+    /// The default value is `None`, meaning the target is unknown.
+    #[must_use]
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
     /// Builds [`PublicApi`]. See the [top level][`crate`] module docs for
     /// example code.
     ///
     /// # Errors
     ///
     /// E.g. if the [JSON](Builder::from_rustdoc_json) is invalid or if the file
-    /// can't be read.
+    /// can't be read, or if [`Self::strict()`] is enabled and the public API
+    /// can't be fully represented.
     pub fn build(self) -> Result<PublicApi> {
-        from_rustdoc_json_str(std::fs::read_to_string(self.rustdoc_json)?, self.options)
+        self.build_with_visitor(|_, _| {})
+    }
+
+    /// Like [`Self::build()`], but `visitor` is called once per [`PublicItem`]
+    /// together with the rustdoc JSON [`rustdoc_types::Item`] it was rendered
+    /// from. This allows you to collect additional data, such as docs or
+    /// attributes, without having to parse the rustdoc JSON a second time.
+    ///
+    /// The visitor is called in processing order, i.e. before items are
+    /// sorted.
+    ///
+    /// # Errors
+    ///
+    /// E.g. if the [JSON](Builder::from_rustdoc_json) is invalid or if the file
+    /// can't be read.
+    pub fn build_with_visitor(
+        self,
+        visitor: impl FnMut(&PublicItem, &rustdoc_types::Item),
+    ) -> Result<PublicApi> {
+        let mut public_api = from_rustdoc_json_str(
+            std::fs::read_to_string(self.rustdoc_json)?,
+            self.options,
+            visitor,
+        )?;
+        public_api.target = self.target;
+        Ok(public_api)
+    }
+
+    /// Diffs the public APIs of two rustdoc JSON strings, e.g. ones downloaded
+    /// from docs.rs, without having to write them to files first. Both public
+    /// APIs are built with the options of this builder, but from `old` and
+    /// `new` instead of from the [file](Builder::from_rustdoc_json).
+    ///
+    /// # Errors
+    ///
+    /// [`Error::SerdeJsonError`] if `old` or `new` can't be parsed. `old` is
+    /// parsed first, so if both are invalid, the error is about `old`.
+    #[cfg(feature = "diff")]
+    pub fn diff_rustdoc_json_strs(&self, old: &str, new: &str) -> Result<diff::PublicApiDiff> {
+        let build = |rustdoc_json_str| -> Result<PublicApi> {
+            let mut public_api =
+                from_rustdoc_json_str(rustdoc_json_str, self.options.clone(), |_, _| {})?;
+            public_api.target = self.target.clone();
+            Ok(public_api)
+        };
+        let old = build(old)?;
+        let new = build(new)?;
+        Ok(diff::PublicApiDiff::between(old, new))
+    }
+
+    /// Like [`Self::build()`], but each [`PublicItem`] is passed to `item_fn`
+    /// as soon as it has been rendered, instead of all items being collected
+    /// into a [`PublicApi`]. Use this to e.g. write items of large crates
+    /// somewhere without holding all rendered items in memory at once.
+    ///
+    /// Items are passed in processing order, i.e. [`Self::sort_mode()`] is
+    /// ignored, since sorting requires all items to be collected first.
+    ///
+    /// # Errors
+    ///
+    /// E.g. if the [JSON](Builder::from_rustdoc_json) is invalid or if the file
+    /// can't be read.
+    pub fn for_each_item(self, mut item_fn: impl FnMut(PublicItem)) -> Result<()> {
+        let crate_: rustdoc_types::Crate =
+            deserialize_without_recursion_limit(&std::fs::read_to_string(self.rustdoc_json)?)?;
+        item_processor::for_each_public_item_in_crate(&crate_, self.options, |item, _| {
+            item_fn(item);
+        })?;
+        Ok(())
     }
 }
 
@@ -182,6 +723,19 @@ impl Builder {
 ///
 /// Create an instance with [`Builder`].
 ///
+/// ## Trait impls and default methods
+///
+/// For an `impl Trait for Type`, only the methods that the impl itself
+/// defines are included, i.e. methods that override a default method of the
+/// trait are listed, but inherited default methods are not. That way, adding
+/// a default method to a trait only shows up as a change to the trait, and not
+/// as a change to every impl of it.
+///
+/// Items of an impl are sorted right after the `impl` line itself, which
+/// includes the bounds of the impl. So a method that is only available if
+/// e.g. `T: Clone` is listed under `impl<T: Clone> Foo<T>`, and adding a
+/// bound to an impl shows up as a changed `impl` line in diffs.
+///
 /// ## Rendering the items
 ///
 /// To render the items in the public API you can iterate over the [items](PublicItem).
@@ -217,6 +771,12 @@ pub struct PublicApi {
 
     /// See [`Self::missing_item_ids()`]
     pub(crate) missing_item_ids: Vec<String>,
+
+    /// See [`Self::target()`]
+    pub(crate) target: Option<String>,
+
+    /// See [`Self::crate_name()`]
+    pub(crate) crate_name: Option<String>,
 }
 
 impl PublicApi {
@@ -245,6 +805,20 @@ impl PublicApi {
     pub fn missing_item_ids(&self) -> impl Iterator<Item = &String> {
         self.missing_item_ids.iter()
     }
+
+    /// The target triple this public API was built for, if it was specified
+    /// with [`Builder::target()`].
+    #[must_use]
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// The name of the crate this public API belongs to, as given by the root
+    /// module in the rustdoc JSON.
+    #[must_use]
+    pub fn crate_name(&self) -> Option<&str> {
+        self.crate_name.as_deref()
+    }
 }
 
 impl std::fmt::Display for PublicApi {
@@ -259,12 +833,15 @@ impl std::fmt::Display for PublicApi {
 fn from_rustdoc_json_str(
     rustdoc_json_str: impl AsRef<str>,
     options: BuilderOptions,
+    visitor: impl FnMut(&PublicItem, &rustdoc_types::Item),
 ) -> Result<PublicApi> {
-    let crate_ = deserialize_without_recursion_limit(rustdoc_json_str.as_ref())?;
+    let crate_: rustdoc_types::Crate =
+        deserialize_without_recursion_limit(rustdoc_json_str.as_ref())?;
 
-    let mut public_api = item_processor::public_api_in_crate(&crate_, options);
+    let sort_mode = options.sort_mode;
+    let mut public_api = item_processor::public_api_in_crate(&crate_, options, visitor)?;
 
-    if options.sorted {
+    if sort_mode == SortMode::Sorted {
         public_api.items.sort_by(PublicItem::grouping_cmp);
     }
 
@@ -274,7 +851,9 @@ fn from_rustdoc_json_str(
 /// Helper to deserialize the JSON with `serde_json`, but with the recursion
 /// limit disabled. Otherwise we hit the recursion limit on crates such as
 /// `diesel`.
-fn deserialize_without_recursion_limit(rustdoc_json_str: &str) -> Result<rustdoc_types::Crate> {
+fn deserialize_without_recursion_limit<T: serde::de::DeserializeOwned>(
+    rustdoc_json_str: &str,
+) -> Result<T> {
     let mut deserializer = serde_json::Deserializer::from_str(rustdoc_json_str);
     deserializer.disable_recursion_limit();
     Ok(serde::de::Deserialize::deserialize(&mut deserializer)?)
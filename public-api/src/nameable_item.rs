@@ -1,5 +1,6 @@
 use rustdoc_types::{Item, ItemEnum};
 
+use crate::item_processor::formatted_sorting_prefix;
 use crate::render::RenderingContext;
 
 /// Wraps an [`Item`] and allows us to override its name.
@@ -30,9 +31,7 @@ impl<'c> NameableItem<'c> {
     /// The name that, when sorted on, will group items nicely. Is never shown
     /// to a user.
     pub fn sortable_name(&self, context: &RenderingContext) -> String {
-        // Note that in order for the prefix to sort properly lexicographically,
-        // we need to pad it with leading zeroes.
-        let mut sortable_name = format!("{:0>3}-", self.sorting_prefix);
+        let mut sortable_name = formatted_sorting_prefix(self.sorting_prefix);
 
         if let Some(name) = self.name() {
             sortable_name.push_str(name);
@@ -0,0 +1,17 @@
+/// How much of the path of an item to render. Only affects how items are
+/// displayed. Sorting and diffing always use the full path, so items that end
+/// up rendered the same way are still kept apart.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More styles might be added in the future
+pub enum PathStyle {
+    /// The full path including the crate name, like `pub fn my_crate::module::foo()`
+    #[default]
+    Full,
+
+    /// Only the name of the item itself, like `pub fn foo()`
+    LeafOnly,
+
+    /// The path relative to the crate root, like `pub fn module::foo()`. The
+    /// crate root module itself is still rendered as `pub mod my_crate`.
+    CrateRelative,
+}
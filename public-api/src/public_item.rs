@@ -1,8 +1,12 @@
 use std::cmp::Ordering;
 use std::fmt::Display;
 use std::hash::Hash;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
 
 use crate::intermediate_public_item::IntermediatePublicItem;
+use crate::item_kind::ItemKind;
 use crate::render::RenderingContext;
 use crate::tokens::tokens_to_string;
 use crate::tokens::Token;
@@ -17,13 +21,40 @@ pub(crate) type PublicItemPath = Vec<String>;
 /// of the public API of a crate. Implements [`Display`] so it can be printed. It
 /// also implements [`Ord`], but how items are ordered are not stable yet, and
 /// will change in later versions.
-#[derive(Clone)]
+///
+/// Also implements [`Hash`] so that you can put items in e.g. a
+/// [`HashSet`](std::collections::HashSet) for fast lookups, for example to
+/// check if an item is still present in a later version of a crate. Note
+/// however that items are compared by their rendered form. Distinct items can
+/// occasionally render the same way due to limitations or bugs, and will then
+/// be considered equal. Such items would be merged into one in a set.
+///
+/// Can be serialized and deserialized with [`serde`], e.g. to cache a public
+/// API. The serialized format is only guaranteed to be understood by the
+/// same version of this library. See [`crate::diff::ApiIndex`].
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PublicItem {
     /// Read [`crate::item_processor::sorting_prefix()`] docs for more info
     pub(crate) sortable_path: PublicItemPath,
 
     /// The rendered item as a stream of [`Token`]s
     pub(crate) tokens: Vec<Token>,
+
+    /// What kind of item this is
+    pub(crate) kind: ItemKind,
+
+    /// See [`IntermediatePublicItem::in_exhaustive_type()`]
+    pub(crate) in_exhaustive_type: bool,
+
+    /// See [`IntermediatePublicItem::doc_hidden()`]
+    pub(crate) doc_hidden: bool,
+
+    /// See [`IntermediatePublicItem::missing_docs()`]
+    pub(crate) missing_docs: bool,
+
+    /// See [`Self::span()`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) span: Option<(PathBuf, usize)>,
 }
 
 impl PublicItem {
@@ -34,6 +65,42 @@ impl PublicItem {
         PublicItem {
             sortable_path: public_item.sortable_path(context),
             tokens: public_item.render_token_stream(context),
+            kind: ItemKind::from_item(public_item.item()),
+            in_exhaustive_type: public_item.in_exhaustive_type(),
+            doc_hidden: public_item.doc_hidden(),
+            missing_docs: public_item.missing_docs(),
+            span: public_item
+                .item()
+                .span
+                .as_ref()
+                .filter(|_| context.options.include_spans)
+                .map(|span| (span.filename.clone(), span.begin.0)),
+        }
+    }
+
+    /// Creates an item from its path and its rendered form, without going
+    /// through rustdoc JSON. Meant for tests and other synthetic use, e.g. to
+    /// assert that a [`crate::diff::PublicApiDiff`] contains a given item:
+    /// ```
+    /// # use public_api::{ItemKind, PublicItem};
+    /// let item = PublicItem::from_str_parts(ItemKind::Function, &["krate", "f"], "pub fn krate::f()");
+    /// assert_eq!(item.to_string(), "pub fn krate::f()");
+    /// ```
+    /// The item compares equal to a built item that renders the same way, but
+    /// `rendered` is kept as a single [`Token`], and `path` is not prefixed
+    /// for grouping like the paths of built items are. So it sorts with
+    /// [`Self::grouping_cmp()`] and is matched up as a changed item in diffs
+    /// only together with other items created this way.
+    #[must_use]
+    pub fn from_str_parts(kind: ItemKind, path: &[&str], rendered: &str) -> Self {
+        PublicItem {
+            sortable_path: path.iter().map(ToString::to_string).collect(),
+            tokens: vec![Token::identifier(rendered)],
+            kind,
+            in_exhaustive_type: false,
+            doc_hidden: false,
+            missing_docs: false,
+            span: None,
         }
     }
 
@@ -42,9 +109,49 @@ impl PublicItem {
         self.tokens.iter()
     }
 
+    /// The path of the item, e.g. `krate`, `Struct`, `field` for a struct
+    /// field. Always starts with the name of the crate, also for items of
+    /// impls for types of other crates, which are rendered with the path of
+    /// the type, e.g. `pub fn u8::method(&self)`. Items of impls have an extra
+    /// component for the impl itself, which is only meant to group the items
+    /// of an impl together.
+    pub fn path(&self) -> impl Iterator<Item = &str> {
+        self.sortable_path
+            .iter()
+            .map(|component| split_sorting_prefix(component).1)
+    }
+
+    /// What kind of item this is, e.g. a struct or a function
+    #[must_use]
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// Whether the item is marked `#[doc(hidden)]`. Always `false` unless the
+    /// rustdoc JSON was built with `--document-hidden-items`, since rustdoc
+    /// leaves hidden items out otherwise. Not part of the rendered item, and
+    /// thus not considered by the [`PartialEq`] impl.
+    #[must_use]
+    pub fn is_doc_hidden(&self) -> bool {
+        self.doc_hidden
+    }
+
+    /// The file that the item is defined in, relative to the workspace root,
+    /// and the 1-based line that the definition starts on. Always `None`
+    /// unless [`crate::Builder::include_spans()`] is enabled, and for items
+    /// that rustdoc has no span for, such as auto trait impls. Not part of the
+    /// rendered item, and thus not considered by the [`PartialEq`] impl.
+    #[must_use]
+    pub fn span(&self) -> Option<(&Path, usize)> {
+        self.span
+            .as_ref()
+            .map(|(file, line)| (file.as_path(), *line))
+    }
+
     /// Special version of [`cmp`](Ord::cmp) that is used to sort public items in a way that
     /// makes them grouped logically. For example, struct fields will be put
-    /// right after the struct they are part of.
+    /// right after the struct they are part of. Items with the same path are
+    /// ordered by their rendered text, so the order is deterministic.
     #[must_use]
     pub fn grouping_cmp(&self, other: &Self) -> std::cmp::Ordering {
         // This will make e.g. struct and struct fields be grouped together.
@@ -52,22 +159,33 @@ impl PublicItem {
             return ordering;
         }
 
-        // Fall back to lexical sorting if the above is not sufficient
-        self.to_string().cmp(&other.to_string())
+        // Paths can collide, e.g. for items created with `from_str_parts()`.
+        // Break the tie with the full rendering so that the order never
+        // depends on the order in which items were found
+        self.text_bytes().cmp(other.text_bytes())
+    }
+
+    /// The rendered text, without allocating a [`String`] for it.
+    fn text_bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.tokens.iter().flat_map(|token| token.text().bytes())
     }
 }
 
+/// Compares the rendered text, so that how the text is split into tokens does
+/// not matter. See [`PublicItem::from_str_parts()`].
 impl PartialEq for PublicItem {
     fn eq(&self, other: &Self) -> bool {
-        self.tokens == other.tokens
+        self.text_bytes().eq(other.text_bytes())
     }
 }
 
 impl Eq for PublicItem {}
 
+/// Must be consistent with our [`PartialEq`] impl, so we only hash the
+/// rendered text.
 impl Hash for PublicItem {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.tokens.hash(state);
+        self.to_string().hash(state);
     }
 }
 
@@ -94,3 +212,14 @@ fn different_or_none<T: Ord>(a: &T, b: &T) -> Option<Ordering> {
         c => Some(c),
     }
 }
+
+/// Splits e.g. `004-krate` into `004-` and `krate`. Paths of items created
+/// with [`PublicItem::from_str_parts()`] have no sorting prefix.
+pub(crate) fn split_sorting_prefix(component: &str) -> (&str, &str) {
+    match component.as_bytes() {
+        [a, b, c, b'-', ..] if [a, b, c].iter().all(|byte| byte.is_ascii_digit()) => {
+            component.split_at(4)
+        }
+        _ => ("", component),
+    }
+}
@@ -1,17 +1,22 @@
 #![allow(clippy::unused_self)]
+use crate::bound_style::BoundStyle;
 use crate::intermediate_public_item::IntermediatePublicItem;
+use crate::item_processor::sorting_prefix;
 use crate::nameable_item::NameableItem;
 use crate::path_component::PathComponent;
+use crate::path_style::PathStyle;
 use crate::tokens::Token;
 use crate::BuilderOptions as Options;
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::ops::Deref;
 use std::{cmp::Ordering, collections::HashMap, vec};
 
 use rustdoc_types::{
-    Abi, Constant, Crate, FnDecl, FunctionPointer, GenericArg, GenericArgs, GenericBound,
-    GenericParamDef, GenericParamDefKind, Generics, Header, Id, Impl, Item, ItemEnum, MacroKind,
-    Path, PolyTrait, StructKind, Term, Trait, Type, TypeBinding, TypeBindingKind, VariantKind,
-    WherePredicate,
+    Abi, Constant, Crate, Deprecation, FnDecl, FunctionPointer, GenericArg, GenericArgs,
+    GenericBound, GenericParamDef, GenericParamDefKind, Generics, Header, Id, Impl, Item, ItemEnum,
+    MacroKind, Path, PolyTrait, Struct, StructKind, Term, Trait, TraitBoundModifier, Type,
+    TypeBinding, TypeBindingKind, Union, VariantKind, WherePredicate,
 };
 
 /// A simple macro to write `Token::Whitespace` in less characters.
@@ -32,6 +37,11 @@ pub struct RenderingContext<'c> {
     pub id_to_items: HashMap<&'c Id, Vec<&'c IntermediatePublicItem<'c>>>,
 
     pub(crate) options: Options,
+
+    /// The type aliases currently being expanded because of
+    /// [`Options::resolve_type_aliases`]. Used to guard against infinite
+    /// recursion.
+    pub(crate) expanding_type_aliases: RefCell<Vec<Id>>,
 }
 
 impl<'c> RenderingContext<'c> {
@@ -39,14 +49,7 @@ impl<'c> RenderingContext<'c> {
         let item = public_item.item();
         let item_path = public_item.path();
 
-        let mut tokens = vec![];
-
-        for attr in &item.attrs {
-            if attr_relevant_for_public_apis(attr) {
-                tokens.push(Token::Annotation(attr.clone()));
-                tokens.push(ws!());
-            }
-        }
+        let mut tokens = self.render_attributes(item);
 
         let inner_tokens = match &item.inner {
             ItemEnum::Module(_) => self.render_simple(&["mod"], item_path),
@@ -77,7 +80,12 @@ impl<'c> RenderingContext<'c> {
             ItemEnum::Variant(inner) => {
                 let mut output = self.render_simple(&[], item_path);
                 match &inner.kind {
-                    VariantKind::Struct { .. } => {} // Each struct field is printed individually
+                    VariantKind::Struct {
+                        fields,
+                        fields_stripped,
+                    } => {
+                        output.extend(self.render_struct_variant_fields(fields, *fields_stripped));
+                    }
                     VariantKind::Plain => {
                         if let Some(discriminant) = &inner.discriminant {
                             output.extend(equals());
@@ -93,7 +101,7 @@ impl<'c> RenderingContext<'c> {
                 output
             }
             ItemEnum::Function(inner) => self.render_function(
-                self.render_path(item_path),
+                self.render_item_path(item_path),
                 &inner.decl,
                 &inner.generics,
                 &inner.header,
@@ -104,10 +112,13 @@ impl<'c> RenderingContext<'c> {
                 self.render_impl(impl_, item_path, false /* disregard_negativity */)
             }
             ItemEnum::TypeAlias(inner) => {
+                // Where clauses go last, which is where Rust prefers them
+                let generics = self.apply_bound_style(&inner.generics);
                 let mut output = self.render_simple(&["type"], item_path);
-                output.extend(self.render_generics(&inner.generics));
+                output.extend(self.render_generic_param_defs(&generics.params));
                 output.extend(equals());
                 output.extend(self.render_type(&inner.type_));
+                output.extend(self.render_where_predicates(&generics.where_predicates));
                 output
             }
             ItemEnum::AssocType {
@@ -115,20 +126,22 @@ impl<'c> RenderingContext<'c> {
                 bounds,
                 default,
             } => {
+                let generics = self.apply_bound_style(generics);
                 let mut output = self.render_simple(&["type"], item_path);
-                output.extend(self.render_generics(generics));
+                output.extend(self.render_generic_param_defs(&generics.params));
                 output.extend(self.render_generic_bounds_with_colon(bounds));
                 if let Some(ty) = default {
                     output.extend(equals());
                     output.extend(self.render_type(ty));
                 }
+                output.extend(self.render_where_predicates(&generics.where_predicates));
                 output
             }
             ItemEnum::OpaqueTy(_) => self.render_simple(&["opaque", "type"], item_path),
             ItemEnum::Constant(con) => {
                 let mut output = self.render_simple(&["const"], item_path);
                 output.extend(colon());
-                output.extend(self.render_constant(con));
+                output.extend(self.render_const_item(con));
                 output
             }
             ItemEnum::AssocConst { type_, .. } => {
@@ -139,7 +152,7 @@ impl<'c> RenderingContext<'c> {
             }
             ItemEnum::Static(inner) => {
                 let tags = if inner.mutable {
-                    vec!["mut", "static"]
+                    vec!["static", "mut"]
                 } else {
                     vec!["static"]
                 };
@@ -148,10 +161,17 @@ impl<'c> RenderingContext<'c> {
                 output.extend(self.render_type(&inner.type_));
                 output
             }
-            ItemEnum::ForeignType => self.render_simple(&["type"], item_path),
+            ItemEnum::ForeignType => self.render_simple(&["extern", "type"], item_path),
             ItemEnum::Macro(_definition) => {
                 // TODO: _definition contains the whole definition, it would be really neat to get out all possible ways to invoke it
-                let mut output = self.render_simple(&["macro"], item_path);
+                let mut output = vec![];
+                if is_macro_exported_at(item, item_path) {
+                    output.extend(vec![
+                        Token::Annotation(String::from("#[macro_export]")),
+                        ws!(),
+                    ]);
+                }
+                output.extend(self.render_simple(&["macro"], item_path));
                 output.push(Token::symbol("!"));
                 output
             }
@@ -160,7 +180,7 @@ impl<'c> RenderingContext<'c> {
                 output.pop(); // Remove name of macro to possibly wrap it in `#[]`
                 let name = Token::identifier(item.name.as_deref().unwrap_or(""));
                 match inner.kind {
-                    MacroKind::Bang => output.extend(vec![name, Token::symbol("!()")]),
+                    MacroKind::Bang => output.extend(vec![name, Token::symbol("!(..)")]),
                     MacroKind::Attr => {
                         output.extend(vec![Token::symbol("#["), name, Token::symbol("]")]);
                     }
@@ -177,7 +197,7 @@ impl<'c> RenderingContext<'c> {
                 //
                 //   rustup component add rust-docs-json --toolchain nightly
                 //   cargo run -- --rustdoc-json ~/.rustup/toolchains/nightly-x86_64-unknown-linux-gnu/share/doc/rust/json/core.json
-                let mut output = pub_();
+                let mut output = self.render_visibility();
                 output.extend([
                     Token::kind("type"),
                     ws!(),
@@ -189,6 +209,13 @@ impl<'c> RenderingContext<'c> {
 
         tokens.extend(inner_tokens);
 
+        if self.options.include_doc_summary {
+            if let Some(summary) = item.docs.as_deref().and_then(doc_summary) {
+                tokens.push(ws!());
+                tokens.push(Token::Annotation(format!("// {summary}")));
+            }
+        }
+
         tokens
     }
 
@@ -215,31 +242,149 @@ impl<'c> RenderingContext<'c> {
         resolved_fields
     }
 
+    /// Renders the fields of a struct variant inline, e.g. ` { x: usize, .. }`,
+    /// like the types of a tuple variant are. `..` stands for fields that are
+    /// hidden. Each field is still also listed as an item of its own.
+    fn render_struct_variant_fields(&self, fields: &[Id], fields_stripped: bool) -> Vec<Token> {
+        let mut rendered_fields = fields
+            .iter()
+            .filter_map(|id| self.crate_.index.get(id))
+            .filter_map(|field| match &field.inner {
+                ItemEnum::StructField(type_) => {
+                    let mut output = vec![Token::identifier(field.name.as_deref().unwrap_or("_"))];
+                    output.extend(colon());
+                    output.extend(self.render_type(type_));
+                    Some(output)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if fields_stripped {
+            rendered_fields.push(vec![Token::symbol("..")]);
+        }
+
+        let mut output = vec![ws!()];
+        if rendered_fields.is_empty() {
+            output.extend([Token::symbol("{"), Token::symbol("}")]);
+        } else {
+            output.extend(self.render_sequence(
+                vec![Token::symbol("{"), ws!()],
+                vec![ws!(), Token::symbol("}")],
+                comma(),
+                &rendered_fields,
+                Clone::clone,
+            ));
+        }
+        output
+    }
+
     fn render_simple(&self, tags: &[&str], path: &[PathComponent]) -> Vec<Token> {
-        let mut output = pub_();
+        let mut output = self.render_visibility();
         output.extend(
             tags.iter()
                 .flat_map(|t| [Token::kind(*t), ws!()])
                 .collect::<Vec<Token>>(),
         );
-        output.extend(self.render_path(path));
+        output.extend(self.render_item_path(path));
+        output
+    }
+
+    /// All items we render are public, so this is always `pub ` unless the
+    /// user asked us to leave it out.
+    fn render_visibility(&self) -> Vec<Token> {
+        if self.options.omit_visibility {
+            vec![]
+        } else {
+            pub_()
+        }
+    }
+
+    /// Renders the attributes of `item` that are part of its public API, e.g.
+    /// `#[repr(C)]`, each followed by whitespace. All attribute rendering
+    /// goes through here so there is one place to decide what to show.
+    fn render_attributes(&self, item: &Item) -> Vec<Token> {
+        let mut output = vec![];
+        for attr in &item.attrs {
+            if attr_relevant_for_public_apis(attr)
+                || (self.options.include_behavior_attrs && attr_affects_behavior(attr))
+                || (self.options.include_diagnostic_attrs
+                    && matches!(item.inner, ItemEnum::Trait(_))
+                    && attr_is_diagnostic(attr))
+            {
+                output.push(Token::Annotation(attr.clone()));
+                output.push(ws!());
+            }
+        }
+
+        // rustdoc JSON parses `#[deprecated]` for us, so use that rather than
+        // the raw attribute
+        if let Some(deprecation) = &item.deprecation {
+            output.push(Token::Annotation(render_deprecation(deprecation)));
+            output.push(ws!());
+        }
+
         output
     }
 
+    /// Renders the path of the item itself, shortened according to
+    /// [`PathStyle`]. Paths to other items use [`Self::render_path()`].
+    fn render_item_path(&self, path: &[PathComponent]) -> Vec<Token> {
+        let path = match self.options.path_style {
+            PathStyle::Full => path,
+            PathStyle::LeafOnly => path
+                .split_last()
+                .map_or(path, |(last, _)| std::slice::from_ref(last)),
+            PathStyle::CrateRelative => match path {
+                [root, rest @ ..] if !rest.is_empty() && is_crate_root(root.item.item) => rest,
+                _ => path,
+            },
+        };
+        self.render_path(path)
+    }
+
     fn render_path(&self, path: &[PathComponent]) -> Vec<Token> {
         let mut output = vec![];
-        for component in path {
+        for (index, component) in path.iter().enumerate() {
             if component.hide {
                 continue;
             }
 
-            let (tokens, push_a_separator) = component.type_.map_or_else(
-                || self.render_nameable_item(&component.item),
-                |ty| self.render_type_and_separator(ty),
-            );
+            let (tokens, push_a_separator) = match (component.type_, &component.item.item.inner) {
+                (
+                    Some(ty),
+                    ItemEnum::Impl(Impl {
+                        trait_: Some(trait_),
+                        ..
+                    }),
+                ) if self.options.qualified_trait_impl_paths => {
+                    (self.render_qualified_self(ty, trait_), true)
+                }
+                (Some(ty), _) => self.render_type_and_separator(ty),
+                (None, _) => self.render_nameable_item(&component.item),
+            };
 
             output.extend(tokens);
 
+            // The last component renders its own generics, if any
+            if self.options.enum_generics_in_paths && index + 1 < path.len() {
+                if let ItemEnum::Enum(enum_) = &component.item.item.inner {
+                    output.extend(self.render_generic_param_names(&enum_.generics.params));
+                }
+            }
+            if self.options.struct_generics_in_paths
+                && path
+                    .get(index + 1)
+                    .is_some_and(|next| matches!(next.item.item.inner, ItemEnum::StructField(_)))
+            {
+                match &component.item.item.inner {
+                    ItemEnum::Struct(Struct { generics, .. })
+                    | ItemEnum::Union(Union { generics, .. }) => {
+                        output.extend(self.render_generic_param_names(&generics.params));
+                    }
+                    _ => {}
+                }
+            }
+
             if push_a_separator {
                 output.push(Token::symbol("::"));
             }
@@ -262,6 +407,7 @@ impl<'c> RenderingContext<'c> {
                 | ItemEnum::Union(_)
                 | ItemEnum::Enum(_)
                 | ItemEnum::TypeAlias(_)
+                | ItemEnum::ForeignType
         ) {
             Token::type_
         } else {
@@ -340,7 +486,9 @@ impl<'c> RenderingContext<'c> {
             return vec![Token::symbol("_")];
         }; // The `_` in `EnumWithStrippedTupleVariants::DoubleFirstHidden(_, bool)`
         match ty {
-            Type::ResolvedPath(path) => self.render_resolved_path(path),
+            Type::ResolvedPath(path) => self
+                .render_type_alias_target(path)
+                .unwrap_or_else(|| self.render_resolved_path(path)),
             Type::DynTrait(dyn_trait) => self.render_dyn_trait(dyn_trait),
             Type::Generic(name) => vec![Token::generic(name)],
             Type::Primitive(name) => vec![Token::primitive(name)],
@@ -366,25 +514,23 @@ impl<'c> RenderingContext<'c> {
     }
 
     fn render_trait(&self, trait_: &Trait, path: &[PathComponent]) -> Vec<Token> {
-        let mut output = pub_();
+        let mut output = self.render_visibility();
         if trait_.is_unsafe {
             output.extend(vec![Token::qualifier("unsafe"), ws!()]);
         };
         output.extend([Token::kind("trait"), ws!()]);
-        output.extend(self.render_path(path));
-        output.extend(self.render_generics(&trait_.generics));
+        output.extend(self.render_item_path(path));
+        // Supertraits go between the generic params and the where clause
+        let generics = self.apply_bound_style(&trait_.generics);
+        output.extend(self.render_generic_param_defs(&generics.params));
         output.extend(self.render_generic_bounds_with_colon(&trait_.bounds));
+        output.extend(self.render_where_predicates(&generics.where_predicates));
         output
     }
 
     fn render_dyn_trait(&self, dyn_trait: &rustdoc_types::DynTrait) -> Vec<Token> {
         let mut output = vec![];
 
-        let more_than_one = dyn_trait.traits.len() > 1 || dyn_trait.lifetime.is_some();
-        if more_than_one {
-            output.push(Token::symbol("("));
-        }
-
         output.extend(self.render_sequence_if_not_empty(
             vec![Token::keyword("dyn"), ws!()],
             vec![],
@@ -398,10 +544,6 @@ impl<'c> RenderingContext<'c> {
             output.extend(vec![Token::lifetime(lt)]);
         }
 
-        if more_than_one {
-            output.push(Token::symbol(")"));
-        }
-
         output
     }
 
@@ -412,34 +554,14 @@ impl<'c> RenderingContext<'c> {
         generics: &Generics,
         header: &Header,
     ) -> Vec<Token> {
-        let mut output = pub_();
-        if header.unsafe_ {
-            output.extend(vec![Token::qualifier("unsafe"), ws!()]);
-        };
-        if header.const_ {
-            output.extend(vec![Token::qualifier("const"), ws!()]);
-        };
-        if header.async_ {
-            output.extend(vec![Token::qualifier("async"), ws!()]);
-        };
-        if header.abi != Abi::Rust {
-            output.push(match &header.abi {
-                Abi::C { .. } => Token::qualifier("c"),
-                Abi::Cdecl { .. } => Token::qualifier("cdecl"),
-                Abi::Stdcall { .. } => Token::qualifier("stdcall"),
-                Abi::Fastcall { .. } => Token::qualifier("fastcall"),
-                Abi::Aapcs { .. } => Token::qualifier("aapcs"),
-                Abi::Win64 { .. } => Token::qualifier("win64"),
-                Abi::SysV64 { .. } => Token::qualifier("sysV64"),
-                Abi::System { .. } => Token::qualifier("system"),
-                Abi::Other(text) => Token::qualifier(text),
-                Abi::Rust => unreachable!(),
-            });
-            output.push(ws!());
-        }
-
+        // With `#![feature(specialization)]`, a fn can also be `default`. We
+        // would render that right after `pub`, but rustdoc JSON does not
+        // record it, neither on the fn nor on its impl
+        let mut output = self.render_visibility();
+        output.extend(render_fn_qualifiers(header));
         output.extend(vec![Token::kind("fn"), ws!()]);
         output.extend(name);
+        let generics = self.apply_bound_style(generics);
 
         // Generic parameters
         output.extend(self.render_generic_param_defs(&generics.params));
@@ -500,15 +622,59 @@ impl<'c> RenderingContext<'c> {
                         output.push(Token::self_("self"));
                         Some(output)
                     }
-                    _ => None,
+                    _ => Some(self.render_typed_self(ty)),
                 },
-                _ => None,
+                _ => Some(self.render_typed_self(ty)),
             }
         } else {
             None
         }
     }
 
+    /// Renders an arbitrary self type such as `self: Box<Self>` or
+    /// `self: Pin<&mut Self>`, keeping `self` a [`Token::Self_`] like in the
+    /// shorthand forms.
+    fn render_typed_self(&self, ty: &Type) -> Vec<Token> {
+        let mut output = vec![Token::self_("self"), Token::symbol(":"), ws!()];
+        output.extend(self.render_type(ty));
+        output
+    }
+
+    /// If [`Options::resolve_type_aliases`] is enabled and `path` refers to a
+    /// type alias in the crate, renders the aliased type instead of the alias.
+    /// Generic aliases are left alone, since expanding them would require
+    /// substituting their generic parameters. Returns `None` if `path` shall
+    /// be rendered as usual.
+    fn render_type_alias_target(&self, path: &Path) -> Option<Vec<Token>> {
+        if !self.options.resolve_type_aliases {
+            return None;
+        }
+
+        let Some(Item {
+            inner: ItemEnum::TypeAlias(alias),
+            ..
+        }) = self.crate_.index.get(&path.id)
+        else {
+            return None;
+        };
+        if !alias.generics.params.is_empty() {
+            return None;
+        }
+
+        // The compiler rejects cyclic type aliases, but let's not overflow the
+        // stack if we are given odd rustdoc JSON
+        if self.expanding_type_aliases.borrow().contains(&path.id) {
+            return None;
+        }
+
+        self.expanding_type_aliases
+            .borrow_mut()
+            .push(path.id.clone());
+        let output = self.render_type(&alias.type_);
+        self.expanding_type_aliases.borrow_mut().pop();
+        Some(output)
+    }
+
     fn render_resolved_path(&self, path: &Path) -> Vec<Token> {
         let mut output = vec![];
         if let Some(item) = self.best_item_for_id(&path.id) {
@@ -601,12 +767,9 @@ impl<'c> RenderingContext<'c> {
     fn render_array(&self, type_: &Type, len: &str) -> Vec<Token> {
         let mut output = vec![Token::symbol("[")];
         output.extend(self.render_type(type_));
-        output.extend(vec![
-            Token::symbol(";"),
-            ws!(),
-            Token::primitive(len),
-            Token::symbol("]"),
-        ]);
+        output.extend(vec![Token::symbol(";"), ws!()]);
+        output.extend(render_const_expr(len));
+        output.push(Token::symbol("]"));
         output
     }
 
@@ -629,7 +792,8 @@ impl<'c> RenderingContext<'c> {
 
         output.push(Token::keyword("impl"));
 
-        output.extend(self.render_generic_param_defs(&impl_.generics.params));
+        let generics = self.apply_bound_style(&impl_.generics);
+        output.extend(self.render_generic_param_defs(&generics.params));
 
         output.push(ws!());
 
@@ -644,7 +808,7 @@ impl<'c> RenderingContext<'c> {
             output.extend(self.render_type(&impl_.for_));
         }
 
-        output.extend(self.render_where_predicates(&impl_.generics.where_predicates));
+        output.extend(self.render_where_predicates(&generics.where_predicates));
 
         output
     }
@@ -660,7 +824,7 @@ impl<'c> RenderingContext<'c> {
         let mut output = vec![Token::symbol("*")];
         output.push(Token::keyword(if mutable { "mut" } else { "const" }));
         output.push(ws!());
-        output.extend(self.render_type(type_));
+        output.extend(self.render_pointee(type_));
         output
     }
 
@@ -671,13 +835,35 @@ impl<'c> RenderingContext<'c> {
         type_: &Type,
     ) -> Vec<Token> {
         let mut output = vec![Token::symbol("&")];
-        if let Some(lt) = lifetime {
+        if let Some(lt) = lifetime.filter(|lt| !self.is_hidden_lifetime(lt)) {
             output.extend(vec![Token::lifetime(lt), ws!()]);
         }
         if mutable {
             output.extend(vec![Token::keyword("mut"), ws!()]);
         }
+        output.extend(self.render_pointee(type_));
+        output
+    }
+
+    /// Renders the type behind a reference or raw pointer. A trait object or
+    /// `impl Trait` with more than one bound must be parenthesized there,
+    /// because e.g. `&dyn A + B` is not valid Rust. Elsewhere, such as in
+    /// `Box<dyn A + B>` or `[dyn A + B]`, no parentheses are needed.
+    fn render_pointee(&self, type_: &Type) -> Vec<Token> {
+        let needs_parens = match type_ {
+            Type::DynTrait(dyn_trait) => dyn_trait.traits.len() > 1 || dyn_trait.lifetime.is_some(),
+            Type::ImplTrait(bounds) => bounds.len() > 1,
+            _ => false,
+        };
+
+        let mut output = vec![];
+        if needs_parens {
+            output.push(Token::symbol("("));
+        }
         output.extend(self.render_type(type_));
+        if needs_parens {
+            output.push(Token::symbol(")"));
+        }
         output
     }
 
@@ -687,23 +873,28 @@ impl<'c> RenderingContext<'c> {
             (Type::Generic(name), Some(trait_)) if name == "Self" && trait_.name.is_empty() => {
                 output.push(Token::keyword("Self"));
             }
-            (_, trait_) => {
-                if trait_.is_some() {
-                    output.push(Token::symbol("<"));
-                }
-                output.extend(self.render_type(type_));
-                if let Some(trait_) = trait_ {
-                    output.extend(vec![ws!(), Token::keyword("as"), ws!()]);
-                    output.extend(self.render_resolved_path(trait_));
-                    output.push(Token::symbol(">"));
-                }
+            // A trait that renders as nothing would give `<Foo as >::Name`,
+            // so treat it like an inherent associated item
+            (_, Some(trait_)) if !self.render_resolved_path(trait_).is_empty() => {
+                output.extend(self.render_qualified_self(type_, trait_));
             }
+            _ => output.extend(self.render_type(type_)),
         }
         output.push(Token::symbol("::"));
         output.push(Token::identifier(name));
         output
     }
 
+    /// Renders e.g. `<Foo as Trait>`
+    fn render_qualified_self(&self, type_: &Type, trait_: &Path) -> Vec<Token> {
+        let mut output = vec![Token::symbol("<")];
+        output.extend(self.render_type(type_));
+        output.extend(vec![ws!(), Token::keyword("as"), ws!()]);
+        output.extend(self.render_resolved_path(trait_));
+        output.push(Token::symbol(">"));
+        output
+    }
+
     fn render_generic_args(&self, args: &GenericArgs) -> Vec<Token> {
         match args {
             GenericArgs::AngleBracketed { args, bindings } => {
@@ -723,9 +914,14 @@ impl<'c> RenderingContext<'c> {
             inputs,
             |type_| self.render_type(type_),
         );
-        if let Some(return_ty) = return_ty {
-            output.extend(arrow());
-            output.extend(self.render_type(return_ty));
+        match return_ty {
+            // `Fn(i32) -> ()` is written as `Fn(i32)`
+            None => {}
+            Some(Type::Tuple(types)) if types.is_empty() => {}
+            Some(return_ty) => {
+                output.extend(arrow());
+                output.extend(self.render_type(return_ty));
+            }
         }
         output
     }
@@ -741,6 +937,9 @@ impl<'c> RenderingContext<'c> {
             comma(),
             &args
                 .iter()
+                .filter(
+                    |arg| !matches!(arg, GenericArg::Lifetime(lt) if self.is_hidden_lifetime(lt)),
+                )
                 .map(Arg::GenericArg)
                 .chain(bindings.iter().map(Arg::TypeBinding))
                 .collect::<Vec<_>>(),
@@ -751,6 +950,13 @@ impl<'c> RenderingContext<'c> {
         )
     }
 
+    /// Whether `lifetime` is the elided lifetime `'_` and should be left out
+    /// because of [`crate::Builder::hide_elided_lifetimes()`]. Leaving it out
+    /// of a reference or of generic args does not change what it means.
+    fn is_hidden_lifetime(&self, lifetime: &str) -> bool {
+        self.options.hide_elided_lifetimes && lifetime == "'_"
+    }
+
     fn render_term(&self, term: &Term) -> Vec<Token> {
         match term {
             Term::Type(ty) => self.render_type(ty),
@@ -782,37 +988,51 @@ impl<'c> RenderingContext<'c> {
                 output.extend(self.render_term(term));
             }
             TypeBindingKind::Constraint(bounds) => {
-                output.extend(self.render_generic_bounds(bounds));
+                output.extend(self.render_generic_bounds_with_colon(bounds));
             }
         }
         output
     }
 
+    /// Renders a const generic argument or a const in a binding, e.g. the `3`
+    /// in `Foo<3>`.
     fn render_constant(&self, constant: &Constant) -> Vec<Token> {
-        let mut output = vec![];
-        if constant.is_literal {
-            output.extend(self.render_type(&constant.type_));
-            if let Some(value) = &constant.value {
-                output.extend(equals());
-                if constant.is_literal {
-                    output.push(Token::primitive(value));
-                } else {
-                    output.push(Token::identifier(value));
-                }
-            }
-        } else {
-            output.push(Token::identifier(&constant.expr));
+        render_const_expr(&constant.expr)
+    }
+
+    /// Renders the type and value of a `const` item, e.g. `i32 = -1`. The
+    /// evaluated value is used even if the const is defined by an expression,
+    /// since rustdoc only gives us the expression itself for literals and
+    /// simple paths, and `_` for everything else. If rustdoc could not
+    /// evaluate the const, e.g. because it is a struct, only the type is
+    /// rendered.
+    fn render_const_item(&self, constant: &Constant) -> Vec<Token> {
+        let mut output = self.render_type(&constant.type_);
+        if let Some(value) = &constant.value {
+            output.extend(equals());
+            output.push(Token::primitive(strip_numeric_suffix(value)));
         }
         output
     }
 
     fn render_generics(&self, generics: &Generics) -> Vec<Token> {
+        let generics = self.apply_bound_style(generics);
         let mut output = vec![];
         output.extend(self.render_generic_param_defs(&generics.params));
         output.extend(self.render_where_predicates(&generics.where_predicates));
         output
     }
 
+    /// Moves bounds of generic type parameters according to
+    /// [`crate::Builder::bound_style()`].
+    fn apply_bound_style<'g>(&self, generics: &'g Generics) -> Cow<'g, Generics> {
+        match self.options.bound_style {
+            BoundStyle::AsWritten => Cow::Borrowed(generics),
+            BoundStyle::Inline => Cow::Owned(bounds_to_inline(generics)),
+            BoundStyle::WhereClause => Cow::Owned(bounds_to_where_clause(generics)),
+        }
+    }
+
     fn render_generic_param_defs(&self, params: &[GenericParamDef]) -> Vec<Token> {
         let params_without_synthetics: Vec<_> = params
             .iter()
@@ -834,6 +1054,36 @@ impl<'c> RenderingContext<'c> {
         )
     }
 
+    /// Renders generic params the way they are used rather than declared, i.e.
+    /// `<'a, T>` instead of `<'a, T: Clone>`.
+    fn render_generic_param_names(&self, params: &[GenericParamDef]) -> Vec<Token> {
+        let params_without_synthetics: Vec<_> = params
+            .iter()
+            .filter(|p| {
+                !matches!(
+                    p.kind,
+                    GenericParamDefKind::Type {
+                        synthetic: true,
+                        ..
+                    }
+                )
+            })
+            .collect();
+
+        self.render_sequence_if_not_empty(
+            vec![Token::symbol("<")],
+            vec![Token::symbol(">")],
+            comma(),
+            &params_without_synthetics,
+            |param| match param.kind {
+                GenericParamDefKind::Lifetime { .. } => vec![Token::lifetime(&param.name)],
+                GenericParamDefKind::Type { .. } | GenericParamDefKind::Const { .. } => {
+                    vec![Token::generic(&param.name)]
+                }
+            },
+        )
+    }
+
     fn render_generic_param_def(&self, generic_param_def: &GenericParamDef) -> Vec<Token> {
         let mut output = vec![];
         match &generic_param_def.kind {
@@ -848,27 +1098,37 @@ impl<'c> RenderingContext<'c> {
             }
             GenericParamDefKind::Type { bounds, .. } => {
                 output.push(Token::generic(&generic_param_def.name));
-                output.extend(self.render_generic_bounds_with_colon(bounds));
+                output.extend(
+                    self.render_generic_bounds_with_colon(&self.without_implied_sized(bounds)),
+                );
             }
-            GenericParamDefKind::Const { type_, .. } => {
+            GenericParamDefKind::Const { type_, default } => {
                 output.push(Token::qualifier("const"));
                 output.push(ws!());
                 output.push(Token::identifier(&generic_param_def.name));
                 output.extend(colon());
                 output.extend(self.render_type(type_));
+                if let Some(default) = default {
+                    output.extend(equals());
+                    output.push(Token::identifier(default));
+                }
             }
         }
         output
     }
 
     fn render_where_predicates(&self, where_predicates: &[WherePredicate]) -> Vec<Token> {
+        let where_predicates = where_predicates
+            .iter()
+            .filter_map(|predicate| self.without_implied_sized_predicate(predicate))
+            .collect::<Vec<_>>();
         let mut output = vec![];
         if !where_predicates.is_empty() {
             output.push(ws!());
             output.push(Token::Keyword("where".to_owned()));
             output.push(ws!());
             output.extend(
-                self.render_sequence(vec![], vec![], comma(), where_predicates, |p| {
+                self.render_sequence(vec![], vec![], comma(), &where_predicates, |p| {
                     self.render_where_predicate(p)
                 }),
             );
@@ -877,14 +1137,10 @@ impl<'c> RenderingContext<'c> {
     }
 
     fn render_where_predicate(&self, where_predicate: &WherePredicate) -> Vec<Token> {
-        let mut output = vec![];
+        let mut output =
+            self.render_higher_rank_trait_bounds(where_predicate_generic_params(where_predicate));
         match where_predicate {
-            WherePredicate::BoundPredicate {
-                type_,
-                bounds,
-                generic_params,
-            } => {
-                output.extend(self.render_higher_rank_trait_bounds(generic_params));
+            WherePredicate::BoundPredicate { type_, bounds, .. } => {
                 output.extend(self.render_type(type_));
                 output.extend(self.render_generic_bounds_with_colon(bounds));
             }
@@ -915,10 +1171,17 @@ impl<'c> RenderingContext<'c> {
             GenericBound::TraitBound {
                 trait_,
                 generic_params,
-                ..
+                modifier,
             } => {
                 let mut output = vec![];
                 output.extend(self.render_higher_rank_trait_bounds(generic_params));
+                match modifier {
+                    TraitBoundModifier::None => {}
+                    TraitBoundModifier::Maybe => output.push(Token::symbol("?")),
+                    TraitBoundModifier::MaybeConst => {
+                        output.extend([Token::symbol("~"), Token::keyword("const"), ws!()]);
+                    }
+                }
                 output.extend(self.render_resolved_path(trait_));
                 output
             }
@@ -926,6 +1189,64 @@ impl<'c> RenderingContext<'c> {
         })
     }
 
+    /// `bounds` without plain `Sized` bounds. Generic type parameters are
+    /// `Sized` unless they are `?Sized`, so `T: Sized` means the same as `T`.
+    /// Some rustdoc JSON format versions include the bound and some do not, so
+    /// without this, every generic item would show up as changed when diffing
+    /// across such versions. `?Sized` is kept.
+    fn without_implied_sized(&self, bounds: &[GenericBound]) -> Vec<GenericBound> {
+        bounds
+            .iter()
+            .filter(|bound| !self.is_plain_sized_bound(bound))
+            .cloned()
+            .collect()
+    }
+
+    /// Like [`Self::without_implied_sized()`], but for where predicates on
+    /// generic type parameters. Returns `None` if no bounds remain, so that
+    /// e.g. `where T: Sized` is not rendered at all. `Self: Sized` is kept,
+    /// since `Self` is not implicitly `Sized` in traits.
+    fn without_implied_sized_predicate(
+        &self,
+        predicate: &WherePredicate,
+    ) -> Option<WherePredicate> {
+        match predicate {
+            WherePredicate::BoundPredicate {
+                type_: Type::Generic(name),
+                bounds,
+                generic_params,
+            } if name != "Self" && generic_params.is_empty() => {
+                let remaining = self.without_implied_sized(bounds);
+                if remaining.is_empty() && !bounds.is_empty() {
+                    None
+                } else {
+                    Some(WherePredicate::BoundPredicate {
+                        type_: Type::Generic(name.clone()),
+                        bounds: remaining,
+                        generic_params: vec![],
+                    })
+                }
+            }
+            _ => Some(predicate.clone()),
+        }
+    }
+
+    fn is_plain_sized_bound(&self, bound: &GenericBound) -> bool {
+        let GenericBound::TraitBound {
+            trait_,
+            generic_params,
+            modifier: TraitBoundModifier::None,
+        } = bound
+        else {
+            return false;
+        };
+        let is_sized = match self.crate_.paths.get(&trait_.id) {
+            Some(summary) => summary.path == ["core", "marker", "Sized"],
+            None => trait_.name == "Sized" || trait_.name.ends_with("::Sized"),
+        };
+        is_sized && generic_params.is_empty() && trait_.args.is_none()
+    }
+
     fn render_higher_rank_trait_bounds(&self, generic_params: &[GenericParamDef]) -> Vec<Token> {
         let mut output = vec![];
         if !generic_params.is_empty() {
@@ -969,12 +1290,218 @@ impl<'c> RenderingContext<'c> {
     }
 }
 
+/// Renders a single rustdoc JSON [`Item`] to [`Token`]s, without processing the
+/// rest of the crate. Useful for tools that already work with
+/// [`rustdoc_types`] directly, e.g. to format one item. Note that you must use
+/// the same version of `rustdoc-types` as this crate does.
+///
+/// `parent_path` are the items leading up to `item`, starting with the root
+/// module of the crate, e.g. `[root, module, struct_]` for a field of
+/// `struct_`. If the parent of `item` is an `impl`, the type that is
+/// implemented for is used as the path of `item`, the same way as when
+/// building the full public API with [`crate::Builder`].
+///
+/// Since the rest of the crate is not processed, types that `item` refers to
+/// are rendered with the paths that [`Crate::paths`] has for them.
+#[must_use]
+pub fn render_item(crate_: &Crate, item: &Item, parent_path: &[&Item]) -> Vec<Token> {
+    let context = RenderingContext {
+        crate_,
+        id_to_items: HashMap::new(),
+        options: Options::default(),
+        expanding_type_aliases: RefCell::default(),
+    };
+
+    context.render_item_with_parents(item, parent_path)
+}
+
+impl RenderingContext<'_> {
+    /// See [`render_item()`]. Renders with the options of `self` rather than
+    /// with the default options.
+    fn render_item_with_parents(&self, item: &Item, parent_path: &[&Item]) -> Vec<Token> {
+        let mut path: Vec<PathComponent> = vec![];
+        for item in parent_path.iter().copied().chain(std::iter::once(item)) {
+            let type_ = if let ItemEnum::Impl(impl_) = &item.inner {
+                // Same as when processing all items, render the type instead of the
+                // items leading up to the impl
+                for component in &mut path {
+                    component.hide = true;
+                }
+                Some(&impl_.for_)
+            } else {
+                None
+            };
+
+            path.push(PathComponent {
+                item: NameableItem {
+                    item,
+                    overridden_name: None,
+                    sorting_prefix: sorting_prefix(item),
+                },
+                type_,
+                hide: false,
+            });
+        }
+
+        self.token_stream(&IntermediatePublicItem::new(path))
+    }
+}
+
+/// The first line of `docs`, or `None` if that line is empty.
+fn doc_summary(docs: &str) -> Option<&str> {
+    let first_line = docs.lines().next()?.trim();
+    (!first_line.is_empty()).then_some(first_line)
+}
+
+/// Renders e.g. `#[deprecated(since = "1.2.3", note = "Use bar instead")]`,
+/// leaving out the parts that are not present.
+fn render_deprecation(deprecation: &Deprecation) -> String {
+    let args = [
+        deprecation
+            .since
+            .as_ref()
+            .map(|since| format!("since = {since:?}")),
+        deprecation
+            .note
+            .as_ref()
+            .map(|note| format!("note = {note:?}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    if args.is_empty() {
+        String::from("#[deprecated]")
+    } else {
+        format!("#[deprecated({})]", args.join(", "))
+    }
+}
+
+/// The higher-ranked binder of `predicate`, e.g. the `'a` in `for<'a> &'a T:
+/// Trait`. Currently only bound predicates can have one in rustdoc JSON, but
+/// all predicates are rendered with their binder so that it is not silently
+/// dropped if e.g. equality predicates get one in a future format version.
+fn where_predicate_generic_params(predicate: &WherePredicate) -> &[GenericParamDef] {
+    match predicate {
+        WherePredicate::BoundPredicate { generic_params, .. } => generic_params,
+        WherePredicate::RegionPredicate { .. } | WherePredicate::EqPredicate { .. } => &[],
+    }
+}
+
+/// Splits a const expression such as the `N * 2` in `[u8; N * 2]` into
+/// tokens, so that it does not look like a single literal. Numeric literals
+/// such as `-1` become a single primitive token, like the value of a `const`
+/// item does, so that a leading `-` is never split off. Names such as `N` or
+/// `LEN` in `Self::LEN` become identifiers, and operators and brackets become
+/// symbols.
+fn render_const_expr(expr: &str) -> Vec<Token> {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let is_bracket = |c: char| matches!(c, '(' | ')' | '[' | ']' | '{' | '}');
+
+    let mut output: Vec<Token> = vec![];
+    let mut rest = expr;
+    while let Some(c) = rest.chars().next() {
+        // A `-` is a sign rather than an operator if no operand precedes it
+        let after_operand = match output.iter().rev().find(|t| **t != Token::Whitespace) {
+            Some(Token::Symbol(symbol)) => matches!(symbol.as_str(), ")" | "]" | "}"),
+            Some(_) => true,
+            None => false,
+        };
+        let is_number = c.is_ascii_digit()
+            || (c == '-' && !after_operand && rest[1..].starts_with(|c: char| c.is_ascii_digit()));
+
+        let len = if c.is_whitespace() {
+            output.push(ws!());
+            rest.len() - rest.trim_start().len()
+        } else if is_number {
+            let len = 1 + rest[1..]
+                .find(|c: char| !is_word_char(c) && c != '.')
+                .unwrap_or(rest.len() - 1);
+            output.push(Token::primitive(strip_numeric_suffix(&rest[..len])));
+            len
+        } else if is_word_char(c) {
+            let len = rest.find(|c: char| !is_word_char(c)).unwrap_or(rest.len());
+            let word = &rest[..len];
+            output.push(if matches!(word, "true" | "false") {
+                Token::primitive(word)
+            } else {
+                Token::identifier(word)
+            });
+            len
+        } else if is_bracket(c) {
+            output.push(Token::symbol(c.to_string()));
+            c.len_utf8()
+        } else {
+            // Operators such as `+` or `<<`, and the `::` of paths
+            let len = rest
+                .find(|c: char| is_word_char(c) || is_bracket(c) || c.is_whitespace())
+                .unwrap_or(rest.len());
+            output.push(Token::symbol(&rest[..len]));
+            len
+        };
+        rest = &rest[len..];
+    }
+    output
+}
+
+/// Strips the type suffix of a numeric literal, e.g. `5u8` becomes `5`, and
+/// `1.5_f32` becomes `1.5`. Whether rustdoc includes the suffix in the value
+/// of a const depends on how the value was written and on the rustdoc
+/// version, and the type is rendered anyway. So we always strip the suffix to
+/// not report a const as changed when only the suffix changed. Values that are
+/// not numeric literals are returned as-is.
+fn strip_numeric_suffix(value: &str) -> &str {
+    const INT_SUFFIXES: [&str; 12] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    const FLOAT_SUFFIXES: [&str; 2] = ["f32", "f64"];
+
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if !digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return value;
+    }
+
+    // In e.g. `0x1f32`, `f32` is part of the hex digits and not a suffix
+    let is_hex = digits.starts_with("0x");
+    let suffixes = INT_SUFFIXES
+        .iter()
+        .chain(FLOAT_SUFFIXES.iter().filter(|_| !is_hex));
+    for suffix in suffixes {
+        if let Some(stripped) = value.strip_suffix(suffix) {
+            if stripped.len() > value.len() - digits.len() {
+                return stripped.strip_suffix('_').unwrap_or(stripped);
+            }
+        }
+    }
+
+    value
+}
+
+/// If `item` is the root module of a crate.
+fn is_crate_root(item: &Item) -> bool {
+    matches!(&item.inner, ItemEnum::Module(module) if module.is_crate)
+}
+
+/// Whether `item` is a `#[macro_export]` macro and `path` is where
+/// `#[macro_export]` puts it, i.e. directly in the crate root. The same macro
+/// can also be made available at other paths with `pub use`, which is what
+/// downstream crates then use to name it.
+fn is_macro_exported_at(item: &Item, path: &[PathComponent]) -> bool {
+    item.attrs.iter().any(|attr| attr == "#[macro_export]")
+        && matches!(path, [root, _] if is_crate_root(root.item.item))
+}
+
 /// Our list of allowed attributes comes from
-/// <https://github.com/rust-lang/rust/blob/68d0b29098/src/librustdoc/html/render/mod.rs#L941-L942>
+/// <https://github.com/rust-lang/rust/blob/68d0b29098/src/librustdoc/html/render/mod.rs#L941-L942>,
+/// plus `#[fundamental]` which affects coherence and `#[must_use]` which
+/// affects what downstream crates get linted for. `#[deprecated]` is handled
+/// by [`render_deprecation()`].
 fn attr_relevant_for_public_apis<S: AsRef<str>>(attr: S) -> bool {
     let prefixes = [
         "#[export_name",
+        "#[fundamental",
         "#[link_section",
+        "#[must_use",
         "#[no_mangle",
         "#[non_exhaustive",
         "#[repr",
@@ -989,10 +1516,70 @@ fn attr_relevant_for_public_apis<S: AsRef<str>>(attr: S) -> bool {
     false
 }
 
+/// Attributes that change what callers observe, rendered with
+/// [`Options::include_behavior_attrs`]. `#[track_caller]` changes the location
+/// that panics report, `#[cold]` changes how calls are optimized and
+/// `#[target_feature]` makes calls require the feature. Keep this list
+/// conservative, since each entry adds noise to the output.
+fn attr_affects_behavior<S: AsRef<str>>(attr: S) -> bool {
+    let prefixes = ["#[cold", "#[target_feature", "#[track_caller"];
+
+    prefixes
+        .iter()
+        .any(|prefix| attr.as_ref().starts_with(prefix))
+}
+
+/// Tool attributes in the `diagnostic` namespace, rendered on traits with
+/// [`Options::include_diagnostic_attrs`]. This is an allowlist rather than
+/// all of `#[diagnostic::`, so that attributes added to the namespace in the
+/// future do not suddenly show up in the output.
+fn attr_is_diagnostic<S: AsRef<str>>(attr: S) -> bool {
+    let prefixes = ["#[diagnostic::on_unimplemented"];
+
+    prefixes
+        .iter()
+        .any(|prefix| attr.as_ref().starts_with(prefix))
+}
+
 fn pub_() -> Vec<Token> {
     vec![Token::qualifier("pub"), ws!()]
 }
 
+/// Renders the qualifiers of a function, e.g. `const unsafe `, in the order
+/// the Rust grammar requires: `const`, `async`, `unsafe`, then the ABI. This is
+/// the only place that reads the qualifier flags of a [`Header`], so if a
+/// rustdoc JSON format version changes how e.g. const-ness is represented,
+/// only this function needs to adapt.
+fn render_fn_qualifiers(header: &Header) -> Vec<Token> {
+    let mut output = vec![];
+    if header.const_ {
+        output.extend(vec![Token::qualifier("const"), ws!()]);
+    }
+    if header.async_ {
+        output.extend(vec![Token::qualifier("async"), ws!()]);
+    }
+    if header.unsafe_ {
+        output.extend(vec![Token::qualifier("unsafe"), ws!()]);
+    }
+    if header.abi != Abi::Rust {
+        output.push(match &header.abi {
+            Abi::C { .. } => Token::qualifier("c"),
+            Abi::Cdecl { .. } => Token::qualifier("cdecl"),
+            Abi::Stdcall { .. } => Token::qualifier("stdcall"),
+            Abi::Fastcall { .. } => Token::qualifier("fastcall"),
+            Abi::Aapcs { .. } => Token::qualifier("aapcs"),
+            Abi::Win64 { .. } => Token::qualifier("win64"),
+            Abi::SysV64 { .. } => Token::qualifier("sysV64"),
+            Abi::System { .. } => Token::qualifier("system"),
+            Abi::Other(text) => Token::qualifier(text),
+            Abi::Rust => unreachable!(),
+        });
+        output.push(ws!());
+    }
+
+    output
+}
+
 fn plus() -> Vec<Token> {
     vec![ws!(), Token::symbol("+"), ws!()]
 }
@@ -1013,6 +1600,72 @@ fn arrow() -> Vec<Token> {
     vec![ws!(), Token::symbol("->"), ws!()]
 }
 
+/// Moves where predicates like `T: Clone` into the declaration of `T`, see
+/// [`BoundStyle::Inline`].
+fn bounds_to_inline(generics: &Generics) -> Generics {
+    let mut params = generics.params.clone();
+    let mut where_predicates = vec![];
+    for predicate in &generics.where_predicates {
+        let param_bounds = match predicate {
+            WherePredicate::BoundPredicate {
+                type_: Type::Generic(name),
+                generic_params,
+                ..
+            } if generic_params.is_empty() => params.iter_mut().find_map(|param| match param {
+                GenericParamDef {
+                    name: param_name,
+                    kind:
+                        GenericParamDefKind::Type {
+                            bounds,
+                            synthetic: false,
+                            ..
+                        },
+                } if *param_name == *name => Some(bounds),
+                _ => None,
+            }),
+            _ => None,
+        };
+        match (param_bounds, predicate) {
+            (Some(param_bounds), WherePredicate::BoundPredicate { bounds, .. }) => {
+                param_bounds.extend(bounds.iter().cloned());
+            }
+            _ => where_predicates.push(predicate.clone()),
+        }
+    }
+    Generics {
+        params,
+        where_predicates,
+    }
+}
+
+/// Moves bounds like `T: Clone` out of the declaration of `T` and into the
+/// where clause, see [`BoundStyle::WhereClause`].
+fn bounds_to_where_clause(generics: &Generics) -> Generics {
+    let mut params = generics.params.clone();
+    let mut where_predicates = vec![];
+    for param in &mut params {
+        if let GenericParamDefKind::Type {
+            bounds,
+            synthetic: false,
+            ..
+        } = &mut param.kind
+        {
+            if !bounds.is_empty() {
+                where_predicates.push(WherePredicate::BoundPredicate {
+                    type_: Type::Generic(param.name.clone()),
+                    bounds: std::mem::take(bounds),
+                    generic_params: vec![],
+                });
+            }
+        }
+    }
+    where_predicates.extend(generics.where_predicates.iter().cloned());
+    Generics {
+        params,
+        where_predicates,
+    }
+}
+
 #[cfg(test)]
 mod test {
     macro_rules! s {
@@ -1024,7 +1677,7 @@ mod test {
     use std::collections::HashMap;
 
     use super::*;
-    use rustdoc_types::Id;
+    use rustdoc_types::{Id, Module, Struct, TypeAlias, Visibility};
 
     #[test]
     fn test_type_infer() {
@@ -1090,37 +1743,155 @@ mod test {
     }
 
     #[test]
-    fn test_type_resolved_crate_name() {
+    fn test_type_resolved_lifetime_only_args() {
         assert_render(
             |context| {
                 context.render_type(&Type::ResolvedPath(Path {
-                    name: s!("$crate::name"),
-                    args: None,
+                    name: s!("Foo"),
+                    args: Some(Box::new(GenericArgs::AngleBracketed {
+                        args: vec![GenericArg::Lifetime(s!("'a"))],
+                        bindings: vec![],
+                    })),
                     id: Id(s!("id")),
                 }))
             },
             vec![
-                Token::identifier("$crate"),
-                Token::symbol("::"),
-                Token::type_("name"),
+                Token::type_("Foo"),
+                Token::symbol("<"),
+                Token::lifetime("'a"),
+                Token::symbol(">"),
             ],
-            "$crate::name",
+            "Foo<'a>",
         );
     }
 
     #[test]
-    fn test_type_resolved_name_crate() {
+    fn test_type_resolved_lifetime_and_type_args() {
         assert_render(
             |context| {
                 context.render_type(&Type::ResolvedPath(Path {
-                    name: s!("name::$crate"),
-                    args: None,
+                    name: s!("Ref"),
+                    args: Some(Box::new(GenericArgs::AngleBracketed {
+                        args: vec![
+                            GenericArg::Lifetime(s!("'a")),
+                            GenericArg::Type(Type::Generic(s!("T"))),
+                        ],
+                        bindings: vec![],
+                    })),
                     id: Id(s!("id")),
                 }))
             },
             vec![
-                Token::identifier("name"),
-                Token::symbol("::"),
+                Token::type_("Ref"),
+                Token::symbol("<"),
+                Token::lifetime("'a"),
+                Token::symbol(","),
+                ws!(),
+                Token::generic("T"),
+                Token::symbol(">"),
+            ],
+            "Ref<'a, T>",
+        );
+    }
+
+    #[test]
+    fn test_type_resolved_ref_with_lifetime_as_arg() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::ResolvedPath(Path {
+                    name: s!("PhantomData"),
+                    args: Some(Box::new(GenericArgs::AngleBracketed {
+                        args: vec![GenericArg::Type(Type::BorrowedRef {
+                            lifetime: Some(s!("'a")),
+                            mutable: false,
+                            type_: Box::new(Type::Tuple(vec![])),
+                        })],
+                        bindings: vec![],
+                    })),
+                    id: Id(s!("id")),
+                }))
+            },
+            vec![
+                Token::type_("PhantomData"),
+                Token::symbol("<"),
+                Token::symbol("&"),
+                Token::lifetime("'a"),
+                ws!(),
+                Token::symbol("("),
+                Token::symbol(")"),
+                Token::symbol(">"),
+            ],
+            "PhantomData<&'a ()>",
+        );
+    }
+
+    #[test]
+    fn test_hide_elided_lifetimes() {
+        let crate_ = empty_crate();
+        let render = |hide_elided_lifetimes, type_| {
+            let context = context(
+                &crate_,
+                crate::Builder::from_rustdoc_json("N/A")
+                    .hide_elided_lifetimes(hide_elided_lifetimes),
+            );
+            crate::tokens::tokens_to_string(&context.render_type(&type_))
+        };
+        let reference = |lifetime: &str| Type::BorrowedRef {
+            lifetime: Some(lifetime.to_string()),
+            mutable: false,
+            type_: Box::new(Type::Generic(s!("T"))),
+        };
+        let path = |lifetime: &str| {
+            Type::ResolvedPath(Path {
+                name: s!("Foo"),
+                args: Some(Box::new(GenericArgs::AngleBracketed {
+                    args: vec![GenericArg::Lifetime(lifetime.to_string())],
+                    bindings: vec![],
+                })),
+                id: Id(s!("id")),
+            })
+        };
+
+        assert_eq!(render(false, reference("'_")), "&'_ T");
+        assert_eq!(render(true, reference("'_")), "&T");
+        assert_eq!(render(true, reference("'a")), "&'a T");
+        assert_eq!(render(false, path("'_")), "Foo<'_>");
+        assert_eq!(render(true, path("'_")), "Foo");
+        assert_eq!(render(true, path("'a")), "Foo<'a>");
+    }
+
+    #[test]
+    fn test_type_resolved_crate_name() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::ResolvedPath(Path {
+                    name: s!("$crate::name"),
+                    args: None,
+                    id: Id(s!("id")),
+                }))
+            },
+            vec![
+                Token::identifier("$crate"),
+                Token::symbol("::"),
+                Token::type_("name"),
+            ],
+            "$crate::name",
+        );
+    }
+
+    #[test]
+    fn test_type_resolved_name_crate() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::ResolvedPath(Path {
+                    name: s!("name::$crate"),
+                    args: None,
+                    id: Id(s!("id")),
+                }))
+            },
+            vec![
+                Token::identifier("name"),
+                Token::symbol("::"),
                 Token::type_("$crate"),
             ],
             "name::$crate",
@@ -1222,6 +1993,63 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_type_pointer_to_fn_pointer() {
+        let fn_pointer = Type::FunctionPointer(Box::new(FunctionPointer {
+            decl: FnDecl {
+                inputs: vec![(s!("_"), Type::Primitive(s!("u8")))],
+                output: None,
+                c_variadic: false,
+            },
+            generic_params: vec![],
+            header: Header {
+                const_: false,
+                unsafe_: false,
+                async_: false,
+                abi: Abi::Rust,
+            },
+        }));
+        assert_render(
+            |context| {
+                context.render_type(&Type::RawPointer {
+                    mutable: false,
+                    type_: Box::new(fn_pointer.clone()),
+                })
+            },
+            vec![
+                Token::symbol("*"),
+                Token::keyword("const"),
+                ws!(),
+                Token::kind("fn"),
+                Token::symbol("("),
+                Token::primitive("u8"),
+                Token::symbol(")"),
+            ],
+            "*const fn(u8)",
+        );
+    }
+
+    #[test]
+    fn test_type_pointer_mut_to_slice() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::RawPointer {
+                    mutable: true,
+                    type_: Box::new(Type::Slice(Box::new(Type::Primitive(s!("u8"))))),
+                })
+            },
+            vec![
+                Token::symbol("*"),
+                Token::keyword("mut"),
+                ws!(),
+                Token::symbol("["),
+                Token::primitive("u8"),
+                Token::symbol("]"),
+            ],
+            "*mut [u8]",
+        );
+    }
+
     #[test]
     fn test_type_ref() {
         assert_render(
@@ -1332,28 +2160,1442 @@ mod test {
         );
     }
 
-    fn assert_render(
-        render_fn: impl Fn(RenderingContext) -> Vec<Token>,
-        expected: Vec<Token>,
-        expected_string: &str,
-    ) {
-        let crate_ = Crate {
-            root: Id(String::from("1:2:3")),
-            crate_version: None,
-            includes_private: false,
-            index: HashMap::new(),
-            paths: HashMap::new(),
-            external_crates: HashMap::new(),
-            format_version: 0,
+    #[test]
+    fn test_type_qualified_path_without_trait() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::QualifiedPath {
+                    name: s!("Name"),
+                    args: Box::new(GenericArgs::AngleBracketed {
+                        args: vec![],
+                        bindings: vec![],
+                    }),
+                    self_type: Box::new(Type::Generic(s!("Foo"))),
+                    trait_: None,
+                })
+            },
+            vec![
+                Token::generic("Foo"),
+                Token::symbol("::"),
+                Token::identifier("Name"),
+            ],
+            "Foo::Name",
+        );
+    }
+
+    #[test]
+    fn test_type_qualified_path_with_empty_trait() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::QualifiedPath {
+                    name: s!("Error"),
+                    args: Box::new(GenericArgs::AngleBracketed {
+                        args: vec![],
+                        bindings: vec![],
+                    }),
+                    self_type: Box::new(Type::Generic(s!("__D"))),
+                    trait_: Some(Path {
+                        name: String::new(),
+                        args: None,
+                        id: Id(s!("id")),
+                    }),
+                })
+            },
+            vec![
+                Token::generic("__D"),
+                Token::symbol("::"),
+                Token::identifier("Error"),
+            ],
+            "__D::Error",
+        );
+    }
+
+    #[test]
+    fn test_type_binding_with_gat_lifetime_args() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::ResolvedPath(Path {
+                    name: s!("LendingIterator"),
+                    args: Some(Box::new(GenericArgs::AngleBracketed {
+                        args: vec![],
+                        bindings: vec![TypeBinding {
+                            name: s!("Item"),
+                            args: GenericArgs::AngleBracketed {
+                                args: vec![GenericArg::Lifetime(s!("'a"))],
+                                bindings: vec![],
+                            },
+                            binding: TypeBindingKind::Equality(Term::Type(Type::BorrowedRef {
+                                lifetime: Some(s!("'a")),
+                                mutable: false,
+                                type_: Box::new(Type::Primitive(s!("u8"))),
+                            })),
+                        }],
+                    })),
+                    id: Id(s!("id")),
+                }))
+            },
+            vec![
+                Token::type_("LendingIterator"),
+                Token::symbol("<"),
+                Token::identifier("Item"),
+                Token::symbol("<"),
+                Token::lifetime("'a"),
+                Token::symbol(">"),
+                ws!(),
+                Token::symbol("="),
+                ws!(),
+                Token::symbol("&"),
+                Token::lifetime("'a"),
+                ws!(),
+                Token::primitive("u8"),
+                Token::symbol(">"),
+            ],
+            "LendingIterator<Item<'a> = &'a u8>",
+        );
+    }
+
+    #[test]
+    fn test_type_binding_with_constraint() {
+        let bound = |name: &str| GenericBound::TraitBound {
+            trait_: Path {
+                name: name.to_string(),
+                args: None,
+                id: Id(format!("0:{name}")),
+            },
+            generic_params: vec![],
+            modifier: TraitBoundModifier::None,
         };
-        let builder = crate::Builder::from_rustdoc_json("N/A");
-        let context = RenderingContext {
-            crate_: &crate_,
-            id_to_items: HashMap::new(),
-            options: builder.options,
+
+        assert_render(
+            |context| {
+                context.render_type(&Type::ImplTrait(vec![GenericBound::TraitBound {
+                    trait_: Path {
+                        name: s!("Iterator"),
+                        args: Some(Box::new(GenericArgs::AngleBracketed {
+                            args: vec![],
+                            bindings: vec![TypeBinding {
+                                name: s!("Item"),
+                                args: GenericArgs::AngleBracketed {
+                                    args: vec![],
+                                    bindings: vec![],
+                                },
+                                binding: TypeBindingKind::Constraint(vec![
+                                    bound("Display"),
+                                    bound("Clone"),
+                                ]),
+                            }],
+                        })),
+                        id: Id(s!("0:Iterator")),
+                    },
+                    generic_params: vec![],
+                    modifier: TraitBoundModifier::None,
+                }]))
+            },
+            vec![
+                Token::keyword("impl"),
+                ws!(),
+                Token::type_("Iterator"),
+                Token::symbol("<"),
+                Token::identifier("Item"),
+                Token::symbol(":"),
+                ws!(),
+                Token::type_("Display"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::type_("Clone"),
+                Token::symbol(">"),
+            ],
+            "impl Iterator<Item: Display + Clone>",
+        );
+    }
+
+    #[test]
+    fn test_where_predicates_none() {
+        assert_render(|context| context.render_where_predicates(&[]), vec![], "");
+    }
+
+    #[test]
+    fn test_where_predicates_one() {
+        assert_render(
+            |context| context.render_where_predicates(&[bound_predicate("A", "B")]),
+            vec![
+                ws!(),
+                Token::keyword("where"),
+                ws!(),
+                Token::generic("A"),
+                Token::symbol(":"),
+                ws!(),
+                Token::type_("B"),
+            ],
+            " where A: B",
+        );
+    }
+
+    #[test]
+    fn test_where_predicates_three() {
+        assert_render(
+            |context| {
+                context.render_where_predicates(&[
+                    bound_predicate("A", "B"),
+                    bound_predicate("C", "D"),
+                    bound_predicate("E", "F"),
+                ])
+            },
+            vec![
+                ws!(),
+                Token::keyword("where"),
+                ws!(),
+                Token::generic("A"),
+                Token::symbol(":"),
+                ws!(),
+                Token::type_("B"),
+                Token::symbol(","),
+                ws!(),
+                Token::generic("C"),
+                Token::symbol(":"),
+                ws!(),
+                Token::type_("D"),
+                Token::symbol(","),
+                ws!(),
+                Token::generic("E"),
+                Token::symbol(":"),
+                ws!(),
+                Token::type_("F"),
+            ],
+            " where A: B, C: D, E: F",
+        );
+    }
+
+    #[test]
+    fn test_where_predicate_higher_ranked_borrowed_ref() {
+        let predicate = WherePredicate::BoundPredicate {
+            type_: Type::BorrowedRef {
+                lifetime: Some(s!("'a")),
+                mutable: false,
+                type_: Box::new(Type::Generic(s!("T"))),
+            },
+            bounds: vec![GenericBound::TraitBound {
+                trait_: Path {
+                    name: s!("Iterator"),
+                    args: None,
+                    id: Id(s!("id")),
+                },
+                generic_params: vec![],
+                modifier: TraitBoundModifier::None,
+            }],
+            generic_params: vec![GenericParamDef {
+                name: s!("'a"),
+                kind: GenericParamDefKind::Lifetime { outlives: vec![] },
+            }],
         };
 
-        let actual = render_fn(context);
+        assert_render(
+            |context| context.render_where_predicates(std::slice::from_ref(&predicate)),
+            vec![
+                ws!(),
+                Token::keyword("where"),
+                ws!(),
+                Token::keyword("for"),
+                Token::symbol("<"),
+                Token::lifetime("'a"),
+                Token::symbol(">"),
+                ws!(),
+                Token::symbol("&"),
+                Token::lifetime("'a"),
+                ws!(),
+                Token::generic("T"),
+                Token::symbol(":"),
+                ws!(),
+                Token::type_("Iterator"),
+            ],
+            " where for<'a> &'a T: Iterator",
+        );
+    }
+
+    #[test]
+    fn test_implied_sized_bound_is_not_rendered() {
+        let maybe_sized = GenericBound::TraitBound {
+            trait_: Path {
+                name: s!("Sized"),
+                args: None,
+                id: Id(s!("id")),
+            },
+            generic_params: vec![],
+            modifier: TraitBoundModifier::Maybe,
+        };
+        let type_param = |bounds: Vec<GenericBound>| GenericParamDef {
+            name: s!("T"),
+            kind: GenericParamDefKind::Type {
+                bounds,
+                default: None,
+                synthetic: false,
+            },
+        };
+        let bounds = |predicate: WherePredicate| match predicate {
+            WherePredicate::BoundPredicate { bounds, .. } => bounds,
+            _ => unreachable!(),
+        };
+        let sized = || bounds(bound_predicate("T", "Sized")).remove(0);
+        let clone = || bounds(bound_predicate("T", "Clone")).remove(0);
+
+        let crate_ = empty_crate();
+        let context = context(&crate_, crate::Builder::from_rustdoc_json("N/A"));
+        let render_params = |bounds| {
+            crate::tokens::tokens_to_string(
+                &context.render_generic_param_defs(&[type_param(bounds)]),
+            )
+        };
+        let render_where = |predicates: &[WherePredicate]| {
+            crate::tokens::tokens_to_string(&context.render_where_predicates(predicates))
+        };
+
+        assert_eq!(render_params(vec![sized()]), "<T>");
+        assert_eq!(render_params(vec![sized(), clone()]), "<T: Clone>");
+        assert_eq!(render_params(vec![maybe_sized]), "<T: ?Sized>");
+        assert_eq!(render_where(&[bound_predicate("T", "Sized")]), "");
+        assert_eq!(
+            render_where(&[bound_predicate("T", "Sized"), bound_predicate("U", "Clone")]),
+            " where U: Clone"
+        );
+        assert_eq!(
+            render_where(&[bound_predicate("Self", "Sized")]),
+            " where Self: Sized"
+        );
+    }
+
+    #[test]
+    fn test_where_predicate_eq() {
+        let predicate = WherePredicate::EqPredicate {
+            lhs: Type::Generic(s!("T")),
+            rhs: Term::Type(Type::Primitive(s!("u8"))),
+        };
+
+        // rustdoc JSON can't express a binder on an equality predicate yet.
+        // Once it can, the binder is expected to be rendered first, like for
+        // bound predicates: ` where for<'a> T = &'a u8`
+        assert!(where_predicate_generic_params(&predicate).is_empty());
+        assert_render(
+            |context| context.render_where_predicates(std::slice::from_ref(&predicate)),
+            vec![
+                ws!(),
+                Token::keyword("where"),
+                ws!(),
+                Token::generic("T"),
+                ws!(),
+                Token::symbol("="),
+                ws!(),
+                Token::primitive("u8"),
+            ],
+            " where T = u8",
+        );
+    }
+
+    #[test]
+    fn test_fn_trait_sugar() {
+        let crate_ = empty_crate();
+        let context = context(&crate_, crate::Builder::from_rustdoc_json("N/A"));
+        let where_f = |trait_: Path| {
+            let predicate = WherePredicate::BoundPredicate {
+                type_: Type::Generic(s!("F")),
+                bounds: vec![GenericBound::TraitBound {
+                    trait_,
+                    generic_params: vec![],
+                    modifier: TraitBoundModifier::None,
+                }],
+                generic_params: vec![],
+            };
+            crate::tokens::tokens_to_string(&context.render_where_predicates(&[predicate]))
+        };
+        let i32_ = || Type::Primitive(s!("i32"));
+
+        assert_eq!(
+            where_f(fn_trait(
+                "Fn",
+                vec![i32_()],
+                Some(Type::Primitive(s!("bool")))
+            )),
+            " where F: Fn(i32) -> bool"
+        );
+        assert_eq!(
+            where_f(fn_trait("FnMut", vec![i32_(), i32_()], None)),
+            " where F: FnMut(i32, i32)"
+        );
+        assert_eq!(
+            where_f(fn_trait("FnOnce", vec![], None)),
+            " where F: FnOnce()"
+        );
+        assert_eq!(
+            where_f(fn_trait("Fn", vec![i32_()], Some(Type::Tuple(vec![])))),
+            " where F: Fn(i32)"
+        );
+
+        let boxed_dyn_fn_mut = Type::ResolvedPath(Path {
+            name: s!("Box"),
+            args: Some(Box::new(GenericArgs::AngleBracketed {
+                args: vec![GenericArg::Type(Type::DynTrait(rustdoc_types::DynTrait {
+                    traits: vec![PolyTrait {
+                        trait_: fn_trait(
+                            "FnMut",
+                            vec![Type::BorrowedRef {
+                                lifetime: None,
+                                mutable: false,
+                                type_: Box::new(Type::Primitive(s!("str"))),
+                            }],
+                            None,
+                        ),
+                        generic_params: vec![],
+                    }],
+                    lifetime: None,
+                }))],
+                bindings: vec![],
+            })),
+            id: Id(s!("id")),
+        });
+        assert_eq!(
+            crate::tokens::tokens_to_string(&context.render_type(&boxed_dyn_fn_mut)),
+            "Box<dyn FnMut(&str)>"
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_self_types() {
+        let self_in = |name: &str| {
+            Type::ResolvedPath(Path {
+                name: name.to_string(),
+                args: Some(Box::new(GenericArgs::AngleBracketed {
+                    args: vec![GenericArg::Type(Type::Generic(s!("Self")))],
+                    bindings: vec![],
+                })),
+                id: Id(s!("id")),
+            })
+        };
+        let pinned_mut_self = Type::ResolvedPath(Path {
+            name: s!("Pin"),
+            args: Some(Box::new(GenericArgs::AngleBracketed {
+                args: vec![GenericArg::Type(Type::BorrowedRef {
+                    lifetime: None,
+                    mutable: true,
+                    type_: Box::new(Type::Generic(s!("Self"))),
+                })],
+                bindings: vec![],
+            })),
+            id: Id(s!("id")),
+        });
+
+        let crate_ = empty_crate();
+        let context = context(&crate_, crate::Builder::from_rustdoc_json("N/A"));
+        for (ty, expected) in [
+            (self_in("Box"), "self: Box<Self>"),
+            (self_in("Rc"), "self: Rc<Self>"),
+            (pinned_mut_self, "self: Pin<&mut Self>"),
+        ] {
+            let tokens = context.simplified_self("self", &ty).unwrap();
+            assert_eq!(tokens[0], Token::self_("self"));
+            assert_eq!(crate::tokens::tokens_to_string(&tokens), expected);
+        }
+    }
+
+    #[test]
+    fn test_slices_and_arrays_of_trait_objects() {
+        let crate_ = empty_crate();
+        let context = context(&crate_, crate::Builder::from_rustdoc_json("N/A"));
+        let render = |type_: &Type| crate::tokens::tokens_to_string(&context.render_type(type_));
+        let path = |name: &str, args: Vec<GenericArg>| Path {
+            name: name.to_string(),
+            args: (!args.is_empty()).then(|| {
+                Box::new(GenericArgs::AngleBracketed {
+                    args,
+                    bindings: vec![],
+                })
+            }),
+            id: Id(format!("0:{name}")),
+        };
+        let dyn_ = |traits: &[&str], lifetime: Option<&str>| {
+            Type::DynTrait(rustdoc_types::DynTrait {
+                traits: traits
+                    .iter()
+                    .map(|name| PolyTrait {
+                        trait_: path(name, vec![]),
+                        generic_params: vec![],
+                    })
+                    .collect(),
+                lifetime: lifetime.map(ToString::to_string),
+            })
+        };
+        let ref_ = |type_: Type| Type::BorrowedRef {
+            lifetime: None,
+            mutable: false,
+            type_: Box::new(type_),
+        };
+
+        assert_eq!(
+            render(&Type::Slice(Box::new(ref_(dyn_(&["Trait"], None))))),
+            "[&dyn Trait]"
+        );
+        assert_eq!(
+            render(&Type::Slice(Box::new(ref_(dyn_(&["Trait"], Some("'a")))))),
+            "[&(dyn Trait + 'a)]"
+        );
+        assert_eq!(
+            render(&Type::Slice(Box::new(Type::ResolvedPath(path(
+                "Box",
+                vec![GenericArg::Type(dyn_(&["Trait"], Some("'a")))]
+            ))))),
+            "[Box<dyn Trait + 'a>]"
+        );
+
+        // Not valid Rust, but must not crash
+        assert_eq!(
+            render(&Type::Array {
+                type_: Box::new(Type::ImplTrait(vec![GenericBound::TraitBound {
+                    trait_: path("Trait", vec![]),
+                    generic_params: vec![],
+                    modifier: TraitBoundModifier::None,
+                }])),
+                len: s!("3"),
+            }),
+            "[impl Trait; 3]"
+        );
+
+        let boxed_error = Type::ResolvedPath(path(
+            "Box",
+            vec![GenericArg::Type(dyn_(&["Error", "Send", "Sync"], None))],
+        ));
+        assert_eq!(
+            render(&Type::ResolvedPath(path(
+                "Vec",
+                vec![GenericArg::Type(boxed_error)]
+            ))),
+            "Vec<Box<dyn Error + Send + Sync>>"
+        );
+        assert_eq!(
+            render(&ref_(dyn_(&["Error", "Send", "Sync"], None))),
+            "&(dyn Error + Send + Sync)"
+        );
+    }
+
+    #[test]
+    fn test_impl_trait_bounds() {
+        let trait_bound = |name: &str, modifier| GenericBound::TraitBound {
+            trait_: Path {
+                name: name.to_string(),
+                args: None,
+                id: Id(format!("0:{name}")),
+            },
+            generic_params: vec![],
+            modifier,
+        };
+
+        assert_render(
+            |context| {
+                context.render_type(&Type::ImplTrait(vec![
+                    trait_bound("Clone", TraitBoundModifier::None),
+                    trait_bound("Send", TraitBoundModifier::None),
+                    GenericBound::Outlives(s!("'a")),
+                ]))
+            },
+            vec![
+                Token::keyword("impl"),
+                ws!(),
+                Token::type_("Clone"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::type_("Send"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::lifetime("'a"),
+            ],
+            "impl Clone + Send + 'a",
+        );
+
+        assert_render(
+            |context| {
+                context.render_type(&Type::ImplTrait(vec![
+                    trait_bound("Debug", TraitBoundModifier::None),
+                    trait_bound("Sized", TraitBoundModifier::Maybe),
+                ]))
+            },
+            vec![
+                Token::keyword("impl"),
+                ws!(),
+                Token::type_("Debug"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::symbol("?"),
+                Token::type_("Sized"),
+            ],
+            "impl Debug + ?Sized",
+        );
+
+        assert_render(
+            |context| {
+                context.render_type(&Type::ImplTrait(vec![trait_bound(
+                    "Default",
+                    TraitBoundModifier::MaybeConst,
+                )]))
+            },
+            vec![
+                Token::keyword("impl"),
+                ws!(),
+                Token::symbol("~"),
+                Token::keyword("const"),
+                ws!(),
+                Token::type_("Default"),
+            ],
+            "impl ~const Default",
+        );
+    }
+
+    /// Creates e.g. `Fn(i32) -> bool`
+    fn fn_trait(name: &str, inputs: Vec<Type>, output: Option<Type>) -> Path {
+        Path {
+            name: name.to_string(),
+            args: Some(Box::new(GenericArgs::Parenthesized { inputs, output })),
+            id: Id(format!("0:{name}")),
+        }
+    }
+
+    /// Creates e.g. `T: Trait`
+    fn bound_predicate(generic: &str, trait_: &str) -> WherePredicate {
+        WherePredicate::BoundPredicate {
+            type_: Type::Generic(generic.to_string()),
+            bounds: vec![GenericBound::TraitBound {
+                trait_: Path {
+                    name: trait_.to_string(),
+                    args: None,
+                    id: Id(s!("id")),
+                },
+                generic_params: vec![],
+                modifier: TraitBoundModifier::None,
+            }],
+            generic_params: vec![],
+        }
+    }
+
+    #[test]
+    fn test_bound_style() {
+        let type_param = |name: &str, bounds: Vec<GenericBound>| GenericParamDef {
+            name: name.to_string(),
+            kind: GenericParamDefKind::Type {
+                bounds,
+                default: None,
+                synthetic: false,
+            },
+        };
+        let WherePredicate::BoundPredicate {
+            bounds: clone_bounds,
+            ..
+        } = bound_predicate("T", "Clone")
+        else {
+            unreachable!()
+        };
+        let generics = Generics {
+            params: vec![
+                GenericParamDef {
+                    name: s!("'a"),
+                    kind: GenericParamDefKind::Lifetime { outlives: vec![] },
+                },
+                type_param("T", clone_bounds),
+                type_param("U", vec![]),
+            ],
+            where_predicates: vec![
+                bound_predicate("U", "Debug"),
+                bound_predicate("Self", "Sized"),
+            ],
+        };
+        let crate_ = empty_crate();
+        let render = |bound_style| {
+            let context = context(
+                &crate_,
+                crate::Builder::from_rustdoc_json("N/A").bound_style(bound_style),
+            );
+            crate::tokens::tokens_to_string(&context.render_generics(&generics))
+        };
+
+        assert_eq!(
+            render(BoundStyle::AsWritten),
+            "<'a, T: Clone, U> where U: Debug, Self: Sized"
+        );
+        assert_eq!(
+            render(BoundStyle::WhereClause),
+            "<'a, T, U> where T: Clone, U: Debug, Self: Sized"
+        );
+        assert_eq!(
+            render(BoundStyle::Inline),
+            "<'a, T: Clone, U: Debug> where Self: Sized"
+        );
+    }
+
+    #[test]
+    fn test_bound_style_of_trait_with_supertraits() {
+        let WherePredicate::BoundPredicate {
+            bounds: clone_bounds,
+            ..
+        } = bound_predicate("T", "Clone")
+        else {
+            unreachable!()
+        };
+        let WherePredicate::BoundPredicate {
+            bounds: supertraits,
+            ..
+        } = bound_predicate("Self", "Bar")
+        else {
+            unreachable!()
+        };
+        let trait_ = item(
+            "Foo",
+            ItemEnum::Trait(Trait {
+                is_auto: false,
+                is_unsafe: false,
+                items: vec![],
+                generics: Generics {
+                    params: vec![
+                        GenericParamDef {
+                            name: s!("T"),
+                            kind: GenericParamDefKind::Type {
+                                bounds: clone_bounds,
+                                default: None,
+                                synthetic: false,
+                            },
+                        },
+                        GenericParamDef {
+                            name: s!("U"),
+                            kind: GenericParamDefKind::Type {
+                                bounds: vec![],
+                                default: None,
+                                synthetic: false,
+                            },
+                        },
+                    ],
+                    where_predicates: vec![bound_predicate("U", "Debug")],
+                },
+                bounds: supertraits,
+                implementations: vec![],
+            }),
+        );
+        let crate_ = empty_crate();
+        let render = |bound_style| {
+            let context = context(
+                &crate_,
+                crate::Builder::from_rustdoc_json("N/A").bound_style(bound_style),
+            );
+            crate::tokens::tokens_to_string(&context.render_item_with_parents(&trait_, &[]))
+        };
+
+        assert_eq!(
+            render(BoundStyle::AsWritten),
+            "pub trait Foo<T: Clone, U>: Bar where U: Debug"
+        );
+        assert_eq!(
+            render(BoundStyle::WhereClause),
+            "pub trait Foo<T, U>: Bar where T: Clone, U: Debug"
+        );
+        assert_eq!(
+            render(BoundStyle::Inline),
+            "pub trait Foo<T: Clone, U: Debug>: Bar"
+        );
+    }
+
+    #[test]
+    fn test_render_attributes() {
+        let render = |attrs: &[&str], deprecation: Option<Deprecation>| {
+            let unit_struct = Item {
+                attrs: attrs.iter().map(|attr| String::from(*attr)).collect(),
+                deprecation,
+                ..item(
+                    "Foo",
+                    ItemEnum::Struct(Struct {
+                        kind: StructKind::Unit,
+                        generics: Generics {
+                            params: vec![],
+                            where_predicates: vec![],
+                        },
+                        impls: vec![],
+                    }),
+                )
+            };
+            crate::tokens::tokens_to_string(&render_item(&empty_crate(), &unit_struct, &[]))
+        };
+
+        assert_eq!(
+            render(&["#[fundamental]"], None),
+            "#[fundamental] pub struct Foo"
+        );
+        assert_eq!(
+            render(&["#[non_exhaustive]"], None),
+            "#[non_exhaustive] pub struct Foo"
+        );
+        assert_eq!(render(&["#[repr(C)]"], None), "#[repr(C)] pub struct Foo");
+        assert_eq!(render(&["#[must_use]"], None), "#[must_use] pub struct Foo");
+        assert_eq!(
+            render(&[r#"#[must_use = "reason"]"#], None),
+            r#"#[must_use = "reason"] pub struct Foo"#
+        );
+        assert_eq!(
+            render(&["#[no_mangle]"], None),
+            "#[no_mangle] pub struct Foo"
+        );
+        assert_eq!(
+            render(&["#[repr(u8)]", "#[must_use]"], None),
+            "#[repr(u8)] #[must_use] pub struct Foo"
+        );
+
+        // Attributes not relevant for the public API are not rendered
+        assert_eq!(render(&["#[inline]"], None), "pub struct Foo");
+    }
+
+    #[test]
+    fn test_render_macro_export() {
+        let root = item(
+            "krate",
+            ItemEnum::Module(Module {
+                is_crate: true,
+                items: vec![],
+                is_stripped: false,
+            }),
+        );
+        let module = item(
+            "reexports",
+            ItemEnum::Module(Module {
+                is_crate: false,
+                items: vec![],
+                is_stripped: false,
+            }),
+        );
+        let macro_ = |attrs: Vec<String>| Item {
+            attrs,
+            ..item("m", ItemEnum::Macro(s!("macro_rules! m { () => {} }")))
+        };
+        let render = |item: &Item, parents: &[&Item]| {
+            crate::tokens::tokens_to_string(&render_item(&empty_crate(), item, parents))
+        };
+
+        let exported = macro_(vec![s!("#[macro_export]")]);
+        assert_eq!(
+            render(&exported, &[&root]),
+            "#[macro_export] pub macro krate::m!"
+        );
+        assert_eq!(
+            render(&exported, &[&root, &module]),
+            "pub macro krate::reexports::m!"
+        );
+        assert_eq!(render(&macro_(vec![]), &[&root]), "pub macro krate::m!");
+    }
+
+    #[test]
+    fn test_render_deprecated() {
+        let render = |since: Option<&str>, note: Option<&str>| {
+            let unit_struct = Item {
+                deprecation: Some(Deprecation {
+                    since: since.map(String::from),
+                    note: note.map(String::from),
+                }),
+                ..item(
+                    "Foo",
+                    ItemEnum::Struct(Struct {
+                        kind: StructKind::Unit,
+                        generics: Generics {
+                            params: vec![],
+                            where_predicates: vec![],
+                        },
+                        impls: vec![],
+                    }),
+                )
+            };
+            crate::tokens::tokens_to_string(&render_item(&empty_crate(), &unit_struct, &[]))
+        };
+
+        assert_eq!(render(None, None), "#[deprecated] pub struct Foo");
+        assert_eq!(
+            render(Some("1.2.3"), None),
+            r#"#[deprecated(since = "1.2.3")] pub struct Foo"#
+        );
+        assert_eq!(
+            render(Some("1.2.3"), Some("Use `Bar` instead")),
+            r#"#[deprecated(since = "1.2.3", note = "Use `Bar` instead")] pub struct Foo"#
+        );
+    }
+
+    #[test]
+    fn test_render_const_generic_with_default_and_where_clause() {
+        let buf = item(
+            "Buf",
+            ItemEnum::Struct(Struct {
+                kind: StructKind::Unit,
+                generics: Generics {
+                    params: vec![GenericParamDef {
+                        name: s!("N"),
+                        kind: GenericParamDefKind::Const {
+                            type_: Type::Primitive(s!("usize")),
+                            default: Some(s!("8")),
+                        },
+                    }],
+                    where_predicates: vec![WherePredicate::BoundPredicate {
+                        type_: Type::Array {
+                            type_: Box::new(Type::Tuple(vec![])),
+                            len: s!("N"),
+                        },
+                        bounds: vec![GenericBound::TraitBound {
+                            trait_: Path {
+                                name: s!("Sized"),
+                                args: None,
+                                id: Id(s!("id")),
+                            },
+                            generic_params: vec![],
+                            modifier: TraitBoundModifier::None,
+                        }],
+                        generic_params: vec![],
+                    }],
+                },
+                impls: vec![],
+            }),
+        );
+
+        let tokens = render_item(&empty_crate(), &buf, &[]);
+
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub struct Buf<const N: usize = 8> where [(); N]: Sized"
+        );
+    }
+
+    #[test]
+    fn test_render_type_alias_where_clause() {
+        let alias = item(
+            "Foo",
+            ItemEnum::TypeAlias(TypeAlias {
+                type_: Type::ResolvedPath(Path {
+                    name: s!("Vec"),
+                    args: Some(Box::new(GenericArgs::AngleBracketed {
+                        args: vec![GenericArg::Type(Type::Generic(s!("T")))],
+                        bindings: vec![],
+                    })),
+                    id: Id(s!("id")),
+                }),
+                generics: Generics {
+                    params: vec![GenericParamDef {
+                        name: s!("T"),
+                        kind: GenericParamDefKind::Type {
+                            bounds: vec![],
+                            default: None,
+                            synthetic: false,
+                        },
+                    }],
+                    where_predicates: vec![bound_predicate("T", "Clone")],
+                },
+            }),
+        );
+
+        let tokens = render_item(&empty_crate(), &alias, &[]);
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub type Foo<T> = Vec<T> where T: Clone"
+        );
+    }
+
+    #[test]
+    fn test_resolved_path_without_name_keeps_generic_args() {
+        let mut crate_ = empty_crate();
+        crate_.paths.insert(
+            Id(s!("0:Foo")),
+            rustdoc_types::ItemSummary {
+                crate_id: 0,
+                path: vec![s!("krate"), s!("Foo")],
+                kind: rustdoc_types::ItemKind::Struct,
+            },
+        );
+        let context = context(&crate_, crate::Builder::from_rustdoc_json("N/A"));
+
+        let tokens = context.render_type(&Type::ResolvedPath(Path {
+            name: s!(""),
+            args: Some(Box::new(GenericArgs::AngleBracketed {
+                args: vec![GenericArg::Type(Type::Primitive(s!("u8")))],
+                bindings: vec![],
+            })),
+            id: Id(s!("0:Foo")),
+        }));
+
+        assert_eq!(crate::tokens::tokens_to_string(&tokens), "krate::Foo<u8>");
+    }
+
+    #[test]
+    fn test_resolved_path_prefers_canonical_path_over_name() {
+        let mut crate_ = empty_crate();
+        crate_.paths.insert(
+            Id(s!("0:Foo")),
+            rustdoc_types::ItemSummary {
+                crate_id: 0,
+                path: vec![s!("krate"), s!("inner"), s!("Foo")],
+                kind: rustdoc_types::ItemKind::Struct,
+            },
+        );
+        let context = context(&crate_, crate::Builder::from_rustdoc_json("N/A"));
+
+        let tokens = context.render_type(&Type::ResolvedPath(Path {
+            name: s!("reexport::Foo"),
+            args: None,
+            id: Id(s!("0:Foo")),
+        }));
+
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "krate::inner::Foo"
+        );
+    }
+
+    #[test]
+    fn test_resolve_cyclic_type_alias() {
+        let path_to_alias = Path {
+            name: s!("Cyclic"),
+            id: Id(s!("0:Cyclic")),
+            args: None,
+        };
+        let mut crate_ = empty_crate();
+        crate_.index.insert(
+            path_to_alias.id.clone(),
+            item(
+                "Cyclic",
+                ItemEnum::TypeAlias(TypeAlias {
+                    type_: Type::ResolvedPath(path_to_alias.clone()),
+                    generics: Generics {
+                        params: vec![],
+                        where_predicates: vec![],
+                    },
+                }),
+            ),
+        );
+        let context = context(
+            &crate_,
+            crate::Builder::from_rustdoc_json("N/A").resolve_type_aliases(true),
+        );
+
+        let tokens = context.render_type(&Type::ResolvedPath(path_to_alias));
+
+        assert_eq!(crate::tokens::tokens_to_string(&tokens), "Cyclic");
+    }
+
+    #[test]
+    fn test_render_item() {
+        let root = item(
+            "krate",
+            ItemEnum::Module(Module {
+                is_crate: true,
+                items: vec![],
+                is_stripped: false,
+            }),
+        );
+        let struct_ = item(
+            "Foo",
+            ItemEnum::Struct(Struct {
+                kind: StructKind::Plain {
+                    fields: vec![],
+                    fields_stripped: false,
+                },
+                generics: Generics {
+                    params: vec![],
+                    where_predicates: vec![],
+                },
+                impls: vec![],
+            }),
+        );
+        let field = item("bar", ItemEnum::StructField(Type::Primitive(s!("u8"))));
+
+        let tokens = render_item(&empty_crate(), &field, &[&root, &struct_]);
+
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub krate::Foo::bar: u8"
+        );
+    }
+
+    #[test]
+    fn test_omit_visibility() {
+        let function = item(
+            "foo",
+            ItemEnum::Function(rustdoc_types::Function {
+                decl: FnDecl {
+                    inputs: vec![],
+                    output: None,
+                    c_variadic: false,
+                },
+                generics: Generics {
+                    params: vec![],
+                    where_predicates: vec![],
+                },
+                header: Header {
+                    const_: true,
+                    unsafe_: false,
+                    async_: false,
+                    abi: Abi::Rust,
+                },
+                has_body: true,
+            }),
+        );
+        let crate_ = empty_crate();
+        let context = context(
+            &crate_,
+            crate::Builder::from_rustdoc_json("N/A").omit_visibility(true),
+        );
+
+        let tokens = context.render_item_with_parents(&function, &[]);
+
+        assert_eq!(crate::tokens::tokens_to_string(&tokens), "const fn foo()");
+    }
+
+    #[test]
+    fn test_include_behavior_attrs() {
+        let function = Item {
+            attrs: vec![s!("#[track_caller]"), s!("#[inline]"), s!("#[cold]")],
+            ..item(
+                "foo",
+                ItemEnum::Function(rustdoc_types::Function {
+                    decl: FnDecl {
+                        inputs: vec![],
+                        output: None,
+                        c_variadic: false,
+                    },
+                    generics: Generics {
+                        params: vec![],
+                        where_predicates: vec![],
+                    },
+                    header: Header {
+                        const_: false,
+                        unsafe_: false,
+                        async_: false,
+                        abi: Abi::Rust,
+                    },
+                    has_body: true,
+                }),
+            )
+        };
+        let crate_ = empty_crate();
+        let render = |include_behavior_attrs| {
+            let context = context(
+                &crate_,
+                crate::Builder::from_rustdoc_json("N/A")
+                    .include_behavior_attrs(include_behavior_attrs),
+            );
+
+            crate::tokens::tokens_to_string(&context.render_item_with_parents(&function, &[]))
+        };
+
+        assert_eq!(render(false), "pub fn foo()");
+        assert_eq!(render(true), "#[track_caller] #[cold] pub fn foo()");
+    }
+
+    #[test]
+    fn test_strip_numeric_suffix() {
+        for (value, expected) in [
+            ("5", "5"),
+            ("5u8", "5"),
+            ("5_u8", "5"),
+            ("-1i64", "-1"),
+            ("1_000usize", "1_000"),
+            ("1.5f32", "1.5"),
+            ("2.0_f64", "2.0"),
+            ("0xffu32", "0xff"),
+            ("0x1f32", "0x1f32"),
+            ("u8", "u8"),
+            ("\"5u8\"", "\"5u8\""),
+        ] {
+            assert_eq!(strip_numeric_suffix(value), expected, "for {value}");
+        }
+    }
+
+    #[test]
+    fn test_fn_qualifiers_order() {
+        let header = Header {
+            const_: true,
+            unsafe_: true,
+            async_: false,
+            abi: Abi::Rust,
+        };
+        assert_eq!(
+            crate::tokens::tokens_to_string(&render_fn_qualifiers(&header)),
+            "const unsafe "
+        );
+
+        let header = Header {
+            const_: false,
+            unsafe_: true,
+            async_: true,
+            abi: Abi::C { unwind: false },
+        };
+        assert_eq!(
+            crate::tokens::tokens_to_string(&render_fn_qualifiers(&header)),
+            "async unsafe c "
+        );
+    }
+
+    #[test]
+    fn test_render_static() {
+        let static_ = |name, mutable| {
+            item(
+                name,
+                ItemEnum::Static(rustdoc_types::Static {
+                    type_: Type::Primitive(s!("i8")),
+                    mutable,
+                    expr: s!("42"),
+                }),
+            )
+        };
+
+        let tokens = render_item(&empty_crate(), &static_("ANSWER", false), &[]);
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub static ANSWER: i8"
+        );
+
+        let tokens = render_item(&empty_crate(), &static_("MUT_ANSWER", true), &[]);
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub static mut MUT_ANSWER: i8"
+        );
+    }
+
+    #[test]
+    fn test_render_const() {
+        let const_ = |type_, expr: &str, value: Option<&str>, is_literal| {
+            item(
+                "X",
+                ItemEnum::Constant(Constant {
+                    type_,
+                    expr: expr.to_string(),
+                    value: value.map(String::from),
+                    is_literal,
+                }),
+            )
+        };
+        let i32_ = || Type::Primitive(s!("i32"));
+
+        // `pub const X: i32 = -1;`
+        let tokens = render_item(
+            &empty_crate(),
+            &const_(i32_(), "-1", Some("-1i32"), true),
+            &[],
+        );
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub const X: i32 = -1"
+        );
+        assert_eq!(tokens.last(), Some(&Token::primitive("-1")));
+
+        // `pub const X: i32 = 1 + 2;`
+        let tokens = render_item(
+            &empty_crate(),
+            &const_(i32_(), "_", Some("3i32"), false),
+            &[],
+        );
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub const X: i32 = 3"
+        );
+
+        // `pub const X: i32 = -(1 + 2);`
+        let tokens = render_item(
+            &empty_crate(),
+            &const_(i32_(), "_", Some("-3i32"), false),
+            &[],
+        );
+        assert_eq!(tokens.last(), Some(&Token::primitive("-3")));
+
+        // `pub const X: [u8; 2] = [1, 2];`, which rustdoc does not evaluate
+        let array = Type::Array {
+            type_: Box::new(Type::Primitive(s!("u8"))),
+            len: s!("2"),
+        };
+        let tokens = render_item(&empty_crate(), &const_(array, "_", None, false), &[]);
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub const X: [u8; 2]"
+        );
+    }
+
+    #[test]
+    fn test_render_const_generic_arg() {
+        let arg = |expr: &str| {
+            GenericArg::Const(Constant {
+                type_: Type::Primitive(s!("i32")),
+                expr: expr.to_string(),
+                value: None,
+                is_literal: false,
+            })
+        };
+
+        assert_render(
+            |context| context.render_generic_arg(&arg("-1")),
+            vec![Token::primitive("-1")],
+            "-1",
+        );
+        assert_render(
+            |context| context.render_generic_arg(&arg("N")),
+            vec![Token::identifier("N")],
+            "N",
+        );
+        assert_render(
+            |context| context.render_generic_arg(&arg("{ N + 1 }")),
+            vec![
+                Token::symbol("{"),
+                ws!(),
+                Token::identifier("N"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::primitive("1"),
+                ws!(),
+                Token::symbol("}"),
+            ],
+            "{ N + 1 }",
+        );
+        assert_render(
+            |context| context.render_generic_arg(&arg("{ Self::LEN - 1usize }")),
+            vec![
+                Token::symbol("{"),
+                ws!(),
+                Token::identifier("Self"),
+                Token::symbol("::"),
+                Token::identifier("LEN"),
+                ws!(),
+                Token::symbol("-"),
+                ws!(),
+                Token::primitive("1"),
+                ws!(),
+                Token::symbol("}"),
+            ],
+            "{ Self::LEN - 1 }",
+        );
+    }
+
+    #[test]
+    fn test_render_array_with_expression_len() {
+        assert_render(
+            |context| {
+                context.render_type(&Type::Array {
+                    type_: Box::new(Type::Primitive(s!("u8"))),
+                    len: s!("N * 2"),
+                })
+            },
+            vec![
+                Token::symbol("["),
+                Token::primitive("u8"),
+                Token::symbol(";"),
+                ws!(),
+                Token::identifier("N"),
+                ws!(),
+                Token::symbol("*"),
+                ws!(),
+                Token::primitive("2"),
+                Token::symbol("]"),
+            ],
+            "[u8; N * 2]",
+        );
+        assert_render(
+            |context| {
+                context.render_type(&Type::Array {
+                    type_: Box::new(Type::Primitive(s!("i8"))),
+                    len: s!("(-1 + M)"),
+                })
+            },
+            vec![
+                Token::symbol("["),
+                Token::primitive("i8"),
+                Token::symbol(";"),
+                ws!(),
+                Token::symbol("("),
+                Token::primitive("-1"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::identifier("M"),
+                Token::symbol(")"),
+                Token::symbol("]"),
+            ],
+            "[i8; (-1 + M)]",
+        );
+    }
+
+    #[test]
+    fn test_render_foreign_type() {
+        let tokens = render_item(&empty_crate(), &item("Handle", ItemEnum::ForeignType), &[]);
+        assert_eq!(
+            crate::tokens::tokens_to_string(&tokens),
+            "pub extern type Handle"
+        );
+        assert_eq!(tokens.last(), Some(&Token::type_("Handle")));
+    }
+
+    #[test]
+    fn test_include_doc_summary() {
+        let crate_ = empty_crate();
+        let context = context(
+            &crate_,
+            crate::Builder::from_rustdoc_json("N/A").include_doc_summary(true),
+        );
+        let render = |docs: Option<&str>| {
+            let field = Item {
+                docs: docs.map(String::from),
+                ..item("bar", ItemEnum::StructField(Type::Primitive(s!("u8"))))
+            };
+
+            crate::tokens::tokens_to_string(&context.render_item_with_parents(&field, &[]))
+        };
+
+        assert_eq!(render(None), "pub bar: u8");
+        assert_eq!(render(Some("")), "pub bar: u8");
+        assert_eq!(render(Some("The bar.")), "pub bar: u8 // The bar.");
+        assert_eq!(
+            render(Some("The bar.\n\nMore details.")),
+            "pub bar: u8 // The bar."
+        );
+    }
+
+    fn item(name: &str, inner: ItemEnum) -> Item {
+        Item {
+            id: Id(format!("0:{name}")),
+            crate_id: 0,
+            name: Some(name.to_string()),
+            span: None,
+            visibility: Visibility::Public,
+            docs: None,
+            links: HashMap::new(),
+            attrs: vec![],
+            deprecation: None,
+            inner,
+        }
+    }
+
+    fn empty_crate() -> Crate {
+        Crate {
+            root: Id(String::from("1:2:3")),
+            crate_version: None,
+            includes_private: false,
+            index: HashMap::new(),
+            paths: HashMap::new(),
+            external_crates: HashMap::new(),
+            format_version: 0,
+        }
+    }
+
+    fn context(crate_: &Crate, builder: crate::Builder) -> RenderingContext<'_> {
+        RenderingContext {
+            crate_,
+            id_to_items: HashMap::new(),
+            options: builder.options,
+            expanding_type_aliases: RefCell::default(),
+        }
+    }
+
+    fn assert_render(
+        render_fn: impl Fn(RenderingContext) -> Vec<Token>,
+        expected: Vec<Token>,
+        expected_string: &str,
+    ) {
+        let crate_ = empty_crate();
+        let actual = render_fn(context(&crate_, crate::Builder::from_rustdoc_json("N/A")));
 
         assert_eq!(actual, expected);
         assert_eq!(
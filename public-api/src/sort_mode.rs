@@ -0,0 +1,19 @@
+/// In what order the items of a [`crate::PublicApi`] are listed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More modes might be added in the future
+pub enum SortMode {
+    /// Sorted by path, with related items grouped together, e.g. struct
+    /// fields right after their struct. See
+    /// [`crate::PublicItem::grouping_cmp()`].
+    #[default]
+    Sorted,
+
+    /// In the order items are found when traversing the rustdoc JSON. Not
+    /// meaningful in itself, but the cheapest mode.
+    Unsorted,
+
+    /// In the order items are declared in the source code, i.e. by the file
+    /// name and then by the line of the span that rustdoc recorded for the
+    /// item. Items without a span, such as auto trait impls, come last.
+    SourceOrder,
+}
@@ -1,9 +1,10 @@
 //! Contains all token handling logic.
 #[cfg(doc)]
 use crate::public_item::PublicItem;
+use serde::{Deserialize, Serialize};
 
 /// A token in a rendered [`PublicItem`], used to apply syntax coloring in downstream applications.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Token {
     /// A symbol, like `=` or `::<`
     Symbol(String),
@@ -105,6 +106,55 @@ impl Token {
     }
 }
 
+/// How [`render_tokens()`] renders [`Token::Whitespace`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive] // More styles might be added in the future
+pub enum RenderStyle {
+    /// Render all whitespace, like `pub fn f(x: u8) -> bool`. This is how
+    /// [`PublicItem`]s are displayed.
+    #[default]
+    Spaced,
+
+    /// Only render whitespace that is needed to keep words apart, like `pub fn
+    /// f(x:u8)->bool`. Useful for hashing or when space is scarce.
+    Compact,
+}
+
+/// Renders `tokens` as a string in the given `style`, e.g.
+/// `render_tokens(item.tokens(), RenderStyle::Compact)`.
+pub fn render_tokens<'a>(
+    tokens: impl IntoIterator<Item = &'a Token>,
+    style: RenderStyle,
+) -> String {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    match style {
+        RenderStyle::Spaced => tokens.into_iter().map(Token::text).collect(),
+        RenderStyle::Compact => {
+            let mut output = String::new();
+            for (index, token) in tokens.iter().enumerate() {
+                if matches!(token, Token::Whitespace) {
+                    let previous = output.chars().next_back();
+                    let next = tokens[index + 1..]
+                        .iter()
+                        .find_map(|token| token.text().chars().next());
+                    if previous.is_some_and(is_word_char) && next.is_some_and(is_word_char) {
+                        output.push(' ');
+                    }
+                } else {
+                    output.push_str(token.text());
+                }
+            }
+            output
+        }
+    }
+}
+
+/// Whether `c` can be part of an identifier, keyword, lifetime or literal,
+/// i.e. whether a space next to it might be needed.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '\''
+}
+
 pub(crate) fn tokens_to_string(tokens: &[Token]) -> String {
     tokens.iter().map(Token::text).collect()
 }
@@ -2,13 +2,18 @@
 #![warn(clippy::all)]
 
 use std::{
+    collections::HashSet,
     fs,
     io::Write,
     path::{Path, PathBuf},
 };
 
 use expect_test::expect_file;
-use public_api::Error;
+use public_api::{
+    diff::{Param, ParamDiff, PublicApiDiff, RetypedParam, SemverImpact},
+    tokens::{render_tokens, RenderStyle},
+    BlanketImplPolicy, Error, ItemKind, PathStyle, PublicItem, SortMode,
+};
 
 use tempfile::{tempdir, NamedTempFile, TempDir};
 
@@ -67,6 +72,19 @@ fn omit_blanket_impls() {
     );
 }
 
+#[test]
+fn local_blanket_impls() {
+    // Create independent build dir so all tests can run in parallel
+    let build_dir = tempdir().unwrap();
+
+    // example_api has no blanket impls of its own, so all are omitted
+    assert_public_api(
+        builder_for_crate("../test-apis/example_api-v0.2.0", &build_dir)
+            .blanket_impl_policy(BlanketImplPolicy::LocalOnly),
+        "./expected-output/example_api-v0.2.0-local_blanket_impls.txt",
+    );
+}
+
 #[test]
 fn omit_auto_trait_impls() {
     // Create independent build dir so all tests can run in parallel
@@ -106,6 +124,32 @@ fn no_diff() {
     );
 }
 
+#[test]
+fn items_can_be_looked_up_in_hash_set() {
+    // Create independent build dirs so all tests can run in parallel
+    let build_dir = tempdir().unwrap();
+    let build_dir2 = tempdir().unwrap();
+
+    let old = builder_for_crate("../test-apis/example_api-v0.1.0", &build_dir)
+        .build()
+        .unwrap();
+    let new = builder_for_crate("../test-apis/example_api-v0.2.0", &build_dir2)
+        .build()
+        .unwrap();
+
+    let new_items = new.items().collect::<HashSet<_>>();
+    let still_present = old
+        .items()
+        .filter(|item| new_items.contains(item))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    assert!(still_present.contains(&String::from("pub mod example_api")));
+    assert!(!still_present.contains(&String::from(
+        "pub fn example_api::function(v1_param: example_api::Struct)"
+    )));
+}
+
 #[test]
 fn diff_empty_when_item_moved_between_inherent_impls() {
     let v1 = rustdoc_json_for_lib(
@@ -141,6 +185,476 @@ impl Foo {
     );
 }
 
+#[test]
+fn diff_empty_when_module_renamed_with_rename_table() {
+    let old_json = rustdoc_json_for_lib(
+        r#"
+pub mod old_mod {
+    pub struct S;
+    impl S {
+        pub fn new() -> S { S }
+    }
+    pub fn f(_: &S) {}
+}
+    "#,
+    );
+    let new_json = rustdoc_json_for_lib(
+        r#"
+pub mod new_mod {
+    pub struct S;
+    impl S {
+        pub fn new() -> S { S }
+    }
+    pub fn f(_: &S) {}
+}
+    "#,
+    );
+    let build = |lib: &LibWithJson| {
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .build()
+            .unwrap()
+    };
+    let renames = [(
+        vec![String::from("lib"), String::from("old_mod")],
+        vec![String::from("lib"), String::from("new_mod")],
+    )];
+
+    let diff = PublicApiDiff::between(build(&old_json), build(&new_json));
+    assert!(!diff.removed.is_empty());
+    assert!(!diff.added.is_empty());
+
+    let diff = PublicApiDiff::between_with_renames(build(&old_json), build(&new_json), &renames);
+    assert!(diff.is_empty(), "{diff:#?}");
+}
+
+#[test]
+fn conditional_methods_are_listed_under_their_impl() {
+    let lib = rustdoc_json_for_lib(
+        r#"
+pub struct Foo<T>(pub T);
+impl<T> Foo<T> {
+    pub fn get(&self) -> &T { &self.0 }
+}
+impl<T: Clone> Foo<T> {
+    pub fn dup(&self) -> T { self.0.clone() }
+}
+impl<T> Foo<T> where T: Default {
+    pub fn reset(&mut self) { self.0 = T::default() }
+}
+        "#,
+    );
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .omit_blanket_impls(true)
+        .omit_auto_trait_impls(true)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    // The bounds a method requires are visible on the impl line right above it
+    let line_before = |method: &str| {
+        let index = items.iter().position(|item| item == method).unwrap();
+        items[index - 1].as_str()
+    };
+    assert_eq!(
+        line_before("pub fn lib::Foo::get(&self) -> &T"),
+        "impl<T> lib::Foo<T>"
+    );
+    assert_eq!(
+        line_before("pub fn lib::Foo::dup(&self) -> T"),
+        "impl<T: core::clone::Clone> lib::Foo<T>"
+    );
+    assert_eq!(
+        line_before("pub fn lib::Foo::reset(&mut self)"),
+        "impl<T> lib::Foo<T> where T: core::default::Default"
+    );
+}
+
+#[test]
+fn synthetic_items_equal_built_items() {
+    let v1 = rustdoc_json_for_lib("pub fn a() {}");
+    let v2 = rustdoc_json_for_lib("pub fn a() {}\npub fn b(x: u8) -> bool { x > 0 }");
+    let build = |json_path| {
+        public_api::Builder::from_rustdoc_json(json_path)
+            .build()
+            .unwrap()
+    };
+
+    let diff = PublicApiDiff::between(build(&v1.json_path), build(&v2.json_path));
+
+    assert_eq!(
+        diff.added,
+        vec![PublicItem::from_str_parts(
+            ItemKind::Function,
+            &["lib", "b"],
+            "pub fn lib::b(x: u8) -> bool"
+        )]
+    );
+}
+
+#[test]
+fn item_paths_have_no_sorting_prefixes() {
+    let json = rustdoc_json_for_lib("pub mod m { pub struct S { pub f: u8 } }");
+    let api = public_api::Builder::from_rustdoc_json(&json.json_path)
+        .build()
+        .unwrap();
+    let field = api
+        .items()
+        .find(|item| item.kind() == ItemKind::StructField)
+        .unwrap();
+    assert_eq!(field.path().collect::<Vec<_>>(), vec!["lib", "m", "S", "f"]);
+
+    let synthetic =
+        PublicItem::from_str_parts(ItemKind::Function, &["lib", "f"], "pub fn lib::f()");
+    assert_eq!(synthetic.path().collect::<Vec<_>>(), vec!["lib", "f"]);
+}
+
+#[test]
+fn items_with_colliding_paths_sort_deterministically() {
+    let fmt =
+        |rendered| PublicItem::from_str_parts(ItemKind::Method, &["lib", "Foo", "fmt"], rendered);
+    let debug = fmt("pub fn <lib::Foo as core::fmt::Debug>::fmt(&self)");
+    let display = fmt("pub fn <lib::Foo as core::fmt::Display>::fmt(&self)");
+
+    let mut items = vec![display.clone(), debug.clone()];
+    items.sort_by(PublicItem::grouping_cmp);
+    let mut reversed = vec![debug.clone(), display.clone()];
+    reversed.sort_by(PublicItem::grouping_cmp);
+
+    assert_eq!(items, vec![debug, display]);
+    assert_eq!(items, reversed);
+}
+
+#[test]
+fn undocumented_items() {
+    let json = rustdoc_json_for_lib(
+        r#"
+//! Docs
+/// Docs
+pub struct Documented;
+pub struct Undocumented;
+impl Undocumented {
+    pub fn inherent(&self) {}
+}
+impl std::fmt::Display for Undocumented {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Ok(())
+    }
+}
+        "#,
+    );
+    let items = public_api::Builder::from_rustdoc_json(&json.json_path)
+        .omit_auto_trait_impls(true)
+        .blanket_impl_policy(BlanketImplPolicy::None)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+
+    let undocumented = public_api::undocumented(&items)
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        undocumented,
+        vec![
+            "pub struct lib::Undocumented",
+            "pub fn lib::Undocumented::inherent(&self)",
+        ]
+    );
+}
+
+#[test]
+fn referenced_types() {
+    let json = rustdoc_json_for_lib(
+        r#"
+pub struct Wrapper(pub std::path::PathBuf);
+pub fn name(_: &Wrapper) -> Option<String> {
+    None
+}
+        "#,
+    );
+    let items = public_api::Builder::from_rustdoc_json(&json.json_path)
+        .omit_auto_trait_impls(true)
+        .blanket_impl_policy(BlanketImplPolicy::None)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        public_api::referenced_types(&items)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![
+            "alloc::string::String",
+            "core::option::Option",
+            "lib::Wrapper",
+            "std::path::PathBuf",
+        ]
+    );
+}
+
+#[test]
+fn diff_rustdoc_json_strs() {
+    let old = rustdoc_json_for_lib("pub fn f() {}");
+    let new = rustdoc_json_for_lib("pub fn f(x: u8) {} pub fn g() {}");
+    let read = |lib: &LibWithJson| fs::read_to_string(&lib.json_path).unwrap();
+    let builder = public_api::Builder::from_rustdoc_json(&old.json_path);
+
+    let diff = builder
+        .diff_rustdoc_json_strs(&read(&old), &read(&new))
+        .unwrap();
+    assert_eq!(
+        diff,
+        diff_between_libs("pub fn f() {}", "pub fn f(x: u8) {} pub fn g() {}")
+    );
+
+    let result = builder.diff_rustdoc_json_strs("not json", &read(&new));
+    assert!(matches!(result, Err(Error::SerdeJsonError(_))));
+}
+
+#[test]
+fn param_changes() {
+    let diff = diff_between_libs(
+        "pub struct S { pub f: u8 }
+         impl S { pub fn m(&self, a: u8) -> u8 { a } }",
+        "pub struct S { pub f: u16 }
+         impl S { pub fn m(&mut self, a: u16, v2_param: usize) {} }",
+    );
+    let changed = |name: &str| {
+        diff.changed
+            .iter()
+            .find(|c| c.new.to_string().contains(name))
+            .unwrap()
+    };
+
+    assert_eq!(
+        changed("::m(").param_changes(),
+        Some(ParamDiff {
+            added: vec![Param {
+                name: "v2_param".to_owned(),
+                type_: "usize".to_owned(),
+            }],
+            removed: vec![],
+            retyped: vec![
+                RetypedParam {
+                    name: "self".to_owned(),
+                    old_type: "&Self".to_owned(),
+                    new_type: "&mut Self".to_owned(),
+                },
+                RetypedParam {
+                    name: "a".to_owned(),
+                    old_type: "u8".to_owned(),
+                    new_type: "u16".to_owned(),
+                },
+            ],
+            old_return_type: "u8".to_owned(),
+            new_return_type: "()".to_owned(),
+        })
+    );
+    assert_eq!(changed("::f:").param_changes(), None);
+}
+
+#[test]
+fn async_trait_methods_render_the_same_in_trait_and_impl() {
+    // `comprehensive_api` only uses features that are stable with
+    // `MINIMUM_NIGHTLY_RUST_VERSION`, and async fn in traits is not
+    let json = rustdoc_json_for_lib(
+        r#"
+#![allow(incomplete_features, stable_features)]
+#![feature(async_fn_in_trait)]
+pub trait Trait {
+    async fn method(&self) -> u8;
+}
+pub struct Struct;
+impl Trait for Struct {
+    async fn method(&self) -> u8 {
+        1
+    }
+}
+        "#,
+    );
+    let public_api = public_api::Builder::from_rustdoc_json(&json.json_path)
+        .build()
+        .unwrap();
+
+    let methods = public_api
+        .items()
+        .map(ToString::to_string)
+        .filter(|item| item.contains("::method("))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        methods,
+        vec![
+            "pub async fn lib::Struct::method(&self) -> u8",
+            "pub async fn lib::Trait::method(&self) -> u8",
+        ]
+    );
+}
+
+#[test]
+fn associated_consts_and_types_of_impls_are_listed_under_the_impl() {
+    let lib = |output_type: &str| {
+        format!(
+            "pub trait Tr {{ type Output; const ID: u32; fn get(&self) -> Self::Output; }}
+             pub struct S;
+             impl S {{ pub const INHERENT: u8 = 1; }}
+             impl Tr for S {{
+                 type Output = {output_type};
+                 const ID: u32 = 7;
+                 fn get(&self) -> {output_type} {{ 1 }}
+             }}"
+        )
+    };
+    let json = rustdoc_json_for_lib(&lib("u32"));
+    let items = public_api::Builder::from_rustdoc_json(&json.json_path)
+        .omit_auto_trait_impls(true)
+        .blanket_impl_policy(BlanketImplPolicy::None)
+        .build()
+        .unwrap()
+        .items()
+        .map(ToString::to_string)
+        .skip_while(|item| item != "pub struct lib::S")
+        .take_while(|item| item != "pub trait lib::Tr")
+        .collect::<Vec<_>>();
+    assert_eq!(
+        items,
+        vec![
+            "pub struct lib::S",
+            "impl lib::S",
+            "pub const lib::S::INHERENT: u8",
+            "impl lib::Tr for lib::S",
+            "pub type lib::S::Output = u32",
+            "pub const lib::S::ID: u32",
+            "pub fn lib::S::get(&self) -> u32",
+        ]
+    );
+
+    let diff = diff_between_libs(&lib("u32"), &lib("u64"));
+    assert!(diff.changed.iter().any(|c| {
+        c.old.to_string() == "pub type lib::S::Output = u32"
+            && c.new.to_string() == "pub type lib::S::Output = u64"
+    }));
+}
+
+#[test]
+fn additions_to_non_exhaustive_types_are_minor() {
+    let impact = semver_impact_between_libs(
+        r#"
+#[non_exhaustive]
+pub enum E { A }
+#[non_exhaustive]
+pub struct S { pub a: u8 }
+        "#,
+        r#"
+#[non_exhaustive]
+pub enum E { A, B }
+#[non_exhaustive]
+pub struct S { pub a: u8, pub b: u8 }
+        "#,
+    );
+    assert_eq!(impact, SemverImpact::Minor);
+}
+
+#[test]
+fn variant_added_to_exhaustive_enum_is_major() {
+    let impact = semver_impact_between_libs("pub enum E { A }", "pub enum E { A, B }");
+    assert_eq!(impact, SemverImpact::Major);
+}
+
+#[test]
+fn field_added_to_exhaustive_struct_is_major() {
+    let impact = semver_impact_between_libs(
+        "pub struct S { pub a: u8 }",
+        "pub struct S { pub a: u8, pub b: u8 }",
+    );
+    assert_eq!(impact, SemverImpact::Major);
+}
+
+#[test]
+fn new_exhaustive_types_and_traits_are_minor() {
+    let impact = semver_impact_between_libs(
+        "",
+        "pub enum E { A } pub struct S { pub a: u8 } pub trait T { fn m(&self); }",
+    );
+    assert_eq!(impact, SemverImpact::Minor);
+}
+
+#[test]
+fn field_added_to_exhaustive_tuple_struct_is_major() {
+    let impact =
+        semver_impact_between_libs("pub struct S(pub u8);", "pub struct S(pub u8, pub u8);");
+    assert_eq!(impact, SemverImpact::Major);
+}
+
+#[test]
+fn field_added_to_tuple_struct_with_private_fields_is_minor() {
+    let impact = semver_impact_between_libs(
+        "pub struct S(pub u8, u8);",
+        "pub struct S(pub u8, u8, pub u8);",
+    );
+    assert_eq!(impact, SemverImpact::Minor);
+}
+
+#[test]
+fn field_added_to_tuple_variant_is_major() {
+    let impact = semver_impact_between_libs("pub enum E { A(u8) }", "pub enum E { A(u8, u8) }");
+    assert_eq!(impact, SemverImpact::Major);
+}
+
+#[test]
+fn field_added_to_struct_with_private_fields_is_minor() {
+    let impact = semver_impact_between_libs(
+        "pub struct S { pub a: u8, private: u8 }",
+        "pub struct S { pub a: u8, pub b: u8, private: u8 }",
+    );
+    assert_eq!(impact, SemverImpact::Minor);
+}
+
+#[test]
+fn required_trait_method_added_is_major() {
+    let impact = semver_impact_between_libs(
+        "pub trait T { fn a(&self); }",
+        "pub trait T { fn a(&self); fn b(&self); }",
+    );
+    assert_eq!(impact, SemverImpact::Major);
+}
+
+#[test]
+fn provided_trait_method_added_is_minor() {
+    let impact = semver_impact_between_libs(
+        "pub trait T { fn a(&self); }",
+        "pub trait T { fn a(&self); fn b(&self) {} }",
+    );
+    assert_eq!(impact, SemverImpact::Minor);
+}
+
+#[test]
+fn added_trait_methods_are_bucketed_by_whether_they_have_a_default() {
+    let diff = diff_between_libs(
+        "pub trait T { fn a(&self); }",
+        "pub trait T { fn a(&self); fn required(&self); fn provided(&self) {} }",
+    );
+    let to_strings = |items: Vec<&PublicItem>| {
+        items
+            .into_iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        to_strings(diff.breaking_additions().collect()),
+        vec!["pub fn lib::T::required(&self)"]
+    );
+    assert_eq!(
+        to_strings(diff.non_breaking_additions().collect()),
+        vec!["pub fn lib::T::provided(&self)"]
+    );
+}
+
 #[test]
 fn diff_with_removed_items() {
     // Create independent build dirs so all tests can run in parallel
@@ -203,12 +717,686 @@ fn comprehensive_api_debug_sorting_no_stack_overflow() {
         .to_string();
 }
 
+#[test]
+fn for_each_item_yields_same_items_as_build() {
+    // Create independent build dir so all tests can run in parallel
+    let build_dir = tempdir().unwrap();
+
+    let rustdoc_json = rustdoc_json_path_for_crate("../test-apis/example_api-v0.2.0", &build_dir);
+
+    let mut streamed = vec![];
+    public_api::Builder::from_rustdoc_json(&rustdoc_json)
+        .for_each_item(|item| streamed.push(item))
+        .unwrap();
+    streamed.sort_by(public_api::PublicItem::grouping_cmp);
+
+    let built = public_api::Builder::from_rustdoc_json(&rustdoc_json)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+
+    assert_eq!(streamed, built);
+}
+
+#[test]
+fn source_order() {
+    let lib = rustdoc_json_for_lib("pub fn zebra() {}\npub struct Middle;\npub fn aardvark() {}");
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .sort_mode(SortMode::SourceOrder)
+        .omit_blanket_impls(true)
+        .omit_auto_trait_impls(true)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    // The crate root module begins where `zebra` begins, but is found first
+    assert_eq!(
+        items,
+        vec![
+            "pub mod lib",
+            "pub fn lib::zebra()",
+            "pub struct lib::Middle",
+            "pub fn lib::aardvark()",
+        ]
+    );
+}
+
+#[test]
+fn build_with_visitor_passes_rustdoc_items() {
+    let lib = rustdoc_json_for_lib("/// Docs for foo\npub fn foo() {}");
+
+    let mut docs = vec![];
+    let public_api = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build_with_visitor(|item, rustdoc_item| {
+            if let Some(item_docs) = &rustdoc_item.docs {
+                docs.push((item.to_string(), item_docs.clone()));
+            }
+        })
+        .unwrap();
+
+    assert_eq!(
+        docs,
+        vec![(
+            String::from("pub fn lib::foo()"),
+            String::from("Docs for foo")
+        )]
+    );
+    assert_eq!(public_api.items().count(), 2);
+}
+
+#[test]
+fn enum_generics_in_paths() {
+    let lib = rustdoc_json_for_lib("pub enum E<'a, T> { A(&'a T), B { t: T } }");
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .enum_generics_in_paths(true)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(items.contains(&String::from("pub enum lib::E<'a, T>")));
+    assert!(items.contains(&String::from("pub lib::E<'a, T>::A(&'a T)")));
+    assert!(items.contains(&String::from("pub lib::E<'a, T>::B")));
+    assert!(items.contains(&String::from("pub lib::E<'a, T>::B::t: T")));
+}
+
+#[test]
+fn struct_generics_in_paths() {
+    let lib = rustdoc_json_for_lib(
+        "pub struct S<'a, T, const N: usize> { pub a: &'a [T; N] }
+         impl<'a, T, const N: usize> S<'a, T, N> { pub fn f() {} }",
+    );
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .struct_generics_in_paths(true)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(items.contains(&String::from("pub struct lib::S<'a, T, const N: usize>")));
+    assert!(items.contains(&String::from("pub lib::S<'a, T, N>::a: &'a [T; N]")));
+    assert!(items.contains(&String::from("pub fn lib::S::f()")));
+}
+
+#[test]
+fn path_style() {
+    let lib = rustdoc_json_for_lib("pub mod m { pub struct S; impl S { pub fn f() {} } }");
+    let items_with_style = |path_style| {
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .path_style(path_style)
+            .build()
+            .unwrap()
+            .into_items()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let full = items_with_style(PathStyle::Full);
+    assert!(full.contains(&String::from("pub fn lib::m::S::f()")));
+
+    let crate_relative = items_with_style(PathStyle::CrateRelative);
+    assert!(crate_relative.contains(&String::from("pub mod lib")));
+    assert!(crate_relative.contains(&String::from("pub mod m")));
+    assert!(crate_relative.contains(&String::from("pub fn m::S::f()")));
+
+    let leaf_only = items_with_style(PathStyle::LeafOnly);
+    assert!(leaf_only.contains(&String::from("pub struct S")));
+    assert!(leaf_only.contains(&String::from("pub fn f()")));
+}
+
+#[test]
+fn trait_impl_lists_only_overridden_default_methods() {
+    let lib = rustdoc_json_for_lib(
+        "pub trait T { fn overridden() {} fn inherited() {} } pub struct S; impl T for S { fn overridden() {} }",
+    );
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(items.contains(&String::from("pub fn lib::T::inherited()")));
+    assert!(items.contains(&String::from("pub fn lib::S::overridden()")));
+    assert!(!items.contains(&String::from("pub fn lib::S::inherited()")));
+}
+
+#[test]
+fn resolve_type_aliases() {
+    let lib = rustdoc_json_for_lib(
+        "pub type Bytes = Vec<u8>; pub type Generic<T> = Vec<T>; pub fn f(b: Bytes, g: Generic<u8>) {}",
+    );
+    let items_with_resolve = |resolve_type_aliases| {
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .resolve_type_aliases(resolve_type_aliases)
+            .build()
+            .unwrap()
+            .into_items()
+            .map(|item| item.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let unresolved = items_with_resolve(false);
+    assert!(unresolved.contains(&String::from(
+        "pub fn lib::f(b: lib::Bytes, g: lib::Generic<u8>)"
+    )));
+
+    let resolved = items_with_resolve(true);
+    assert!(resolved.contains(&String::from(
+        "pub fn lib::f(b: alloc::vec::Vec<u8>, g: lib::Generic<u8>)"
+    )));
+    assert!(resolved.contains(&String::from("pub type lib::Bytes = alloc::vec::Vec<u8>")));
+}
+
+#[test]
+fn check_format_version() {
+    let lib = rustdoc_json_for_lib("pub fn f() {}");
+    let rustdoc_json = fs::read_to_string(&lib.json_path).unwrap();
+    assert_eq!(
+        public_api::check_format_version(&rustdoc_json).unwrap(),
+        rustdoc_types::FORMAT_VERSION
+    );
+
+    let result = public_api::check_format_version(r#"{"format_version": 1, "index": {}}"#);
+    assert!(matches!(
+        result,
+        Err(Error::UnsupportedFormatVersion {
+            found: 1,
+            supported: rustdoc_types::FORMAT_VERSION,
+        })
+    ));
+
+    let result = public_api::check_format_version(r#"{"index": {}}"#);
+    assert!(matches!(result, Err(Error::SerdeJsonError(_))));
+}
+
+#[test]
+fn compact_render_style() {
+    let lib = rustdoc_json_for_lib("pub fn f<'a>(x: &'a mut u8, _: [u8; 2]) -> &'a u8 { x }");
+    let item = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .find(|item| item.kind() == ItemKind::Function)
+        .unwrap();
+
+    assert_eq!(
+        render_tokens(item.tokens(), RenderStyle::Spaced),
+        item.to_string()
+    );
+    assert_eq!(
+        render_tokens(item.tokens(), RenderStyle::Compact),
+        "pub fn lib::f<'a>(x:&'a mut u8,[u8;2])->&'a u8"
+    );
+}
+
+#[test]
+fn modules() {
+    let lib = rustdoc_json_for_lib(
+        "pub mod a { pub mod b { pub fn f() {} } } pub mod c {} pub struct S;",
+    );
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        public_api::modules(&items),
+        vec![
+            vec!["lib"],
+            vec!["lib", "a"],
+            vec!["lib", "a", "b"],
+            vec!["lib", "c"],
+        ]
+    );
+
+    // Parent modules are included even if only a nested item is given
+    let f = items
+        .into_iter()
+        .filter(|item| item.to_string() == "pub fn lib::a::b::f()")
+        .collect::<Vec<_>>();
+    assert_eq!(
+        public_api::modules(&f),
+        vec![vec!["lib"], vec!["lib", "a"], vec!["lib", "a", "b"]]
+    );
+}
+
+#[test]
+fn fingerprint() {
+    let lib = rustdoc_json_for_lib("pub fn f() {}");
+    let mut items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+
+    // Must stay the same between runs, so pin the value
+    assert_eq!(public_api::fingerprint(&items), 0xd35e_b264_cf07_1e84);
+    assert_eq!(public_api::fingerprint(&[]), 0xcbf2_9ce4_8422_2325);
+
+    // Does not depend on the order of items
+    items.reverse();
+    assert_eq!(public_api::fingerprint(&items), 0xd35e_b264_cf07_1e84);
+
+    let other_lib = rustdoc_json_for_lib("pub fn g() {}");
+    let other_items = public_api::Builder::from_rustdoc_json(&other_lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+    assert_ne!(
+        public_api::fingerprint(&items),
+        public_api::fingerprint(&other_items)
+    );
+}
+
+#[test]
+fn semantically_equal() {
+    let items = |lib: &str| {
+        let lib = rustdoc_json_for_lib(lib);
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .build()
+            .unwrap()
+            .into_items()
+            .collect::<Vec<_>>()
+    };
+
+    let original = items(
+        r#"
+pub fn f<'a, T: Clone + Send>(x: &'a T) -> Box<dyn std::fmt::Debug + Send + 'a> { todo!() }
+pub fn g() {}
+        "#,
+    );
+    let reordered_and_renamed = items(
+        r#"
+pub fn g() {}
+pub fn f<'b, T: Send + Clone>(x: &'b T) -> Box<dyn Send + std::fmt::Debug + 'b> { todo!() }
+        "#,
+    );
+    let bound_removed = items(
+        r#"
+pub fn f<'a, T: Clone>(x: &'a T) -> Box<dyn std::fmt::Debug + Send + 'a> { todo!() }
+pub fn g() {}
+        "#,
+    );
+
+    assert!(public_api::semantically_equal(
+        &original,
+        &reordered_and_renamed
+    ));
+    assert_eq!(
+        public_api::canonical_hash(&original),
+        public_api::canonical_hash(&reordered_and_renamed)
+    );
+
+    assert!(!public_api::semantically_equal(&original, &bound_removed));
+    assert_ne!(
+        public_api::canonical_hash(&original),
+        public_api::canonical_hash(&bound_removed)
+    );
+}
+
+#[test]
+fn methods_and_associated_functions_have_different_kinds() {
+    let lib = rustdoc_json_for_lib(
+        "pub struct S; impl S { pub fn new() -> Self { S } pub fn get(&self) {} } pub fn free() {}",
+    );
+
+    let kind_of = |rendered: &str| {
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .build()
+            .unwrap()
+            .into_items()
+            .find(|item| item.to_string() == rendered)
+            .unwrap()
+            .kind()
+    };
+
+    assert_eq!(kind_of("pub fn lib::S::new() -> Self"), ItemKind::Function);
+    assert_eq!(kind_of("pub fn lib::S::get(&self)"), ItemKind::Method);
+    assert_eq!(kind_of("pub fn lib::free()"), ItemKind::Function);
+}
+
+#[test]
+fn impl_trait_args_render_as_impl_trait() {
+    let lib = rustdoc_json_for_lib(
+        "pub fn f(x: impl std::fmt::Display) {} pub fn g<T: Clone>(t: T, x: impl Iterator<Item = T>) {}",
+    );
+
+    let items: Vec<_> = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect();
+
+    // The synthetic generic parameters that rustdoc adds for `impl Trait`
+    // arguments must not show up
+    assert_eq!(
+        items,
+        vec![
+            "pub mod lib",
+            "pub fn lib::f(x: impl core::fmt::Display)",
+            "pub fn lib::g<T: core::clone::Clone>(t: T, x: impl core::iter::traits::iterator::Iterator<Item = T>)",
+        ]
+    );
+}
+
+#[test]
+fn hidden_impl_traits() {
+    let lib = rustdoc_json_for_lib(
+        r#"
+#[derive(Debug, Clone)]
+pub struct S;
+impl std::fmt::Display for S {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "S")
+    }
+}
+        "#,
+    );
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .hidden_impl_traits(["Debug", "core::clone::Clone"])
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(items.contains(&String::from("impl core::fmt::Display for lib::S")));
+    assert!(!items.contains(&String::from("impl core::fmt::Debug for lib::S")));
+    assert!(!items.contains(&String::from("impl core::clone::Clone for lib::S")));
+    assert!(!items.contains(&String::from("pub fn lib::S::clone(&self) -> lib::S")));
+}
+
+#[test]
+fn qualified_trait_impl_paths() {
+    let lib = rustdoc_json_for_lib(
+        r#"
+pub struct Foo;
+impl Foo {
+    pub fn bar(&self) {}
+}
+pub trait Trait {
+    fn bar(&self);
+}
+impl Trait for Foo {
+    fn bar(&self) {}
+}
+        "#,
+    );
+
+    let bars = |qualified_trait_impl_paths| {
+        let mut bars = public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .qualified_trait_impl_paths(qualified_trait_impl_paths)
+            .build()
+            .unwrap()
+            .into_items()
+            .map(|item| item.to_string())
+            .filter(|item| item.ends_with("bar(&self)"))
+            .collect::<Vec<_>>();
+        bars.sort();
+        bars.dedup();
+        bars
+    };
+
+    // Without the option, the inherent and the trait impl method look the same
+    assert_eq!(
+        bars(false),
+        [
+            "pub fn lib::Foo::bar(&self)",
+            "pub fn lib::Trait::bar(&self)"
+        ]
+    );
+    assert_eq!(
+        bars(true),
+        [
+            "pub fn <lib::Foo as lib::Trait>::bar(&self)",
+            "pub fn lib::Foo::bar(&self)",
+            "pub fn lib::Trait::bar(&self)",
+        ]
+    );
+}
+
+#[test]
+fn item_paths_start_with_crate_name() {
+    let lib = rustdoc_json_for_lib(
+        r#"
+pub trait Trait {
+    fn method(&self);
+}
+impl Trait for u8 {
+    fn method(&self) {}
+}
+        "#,
+    );
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .omit_auto_trait_impls(true)
+        .blanket_impl_policy(BlanketImplPolicy::None)
+        .build()
+        .unwrap()
+        .into_items()
+        .collect::<Vec<_>>();
+
+    let method = items
+        .iter()
+        .find(|item| item.to_string() == "pub fn u8::method(&self)")
+        .unwrap();
+    assert_eq!(method.path().next(), Some("lib"));
+    for item in &items {
+        assert_eq!(item.path().next(), Some("lib"), "for {item}");
+    }
+}
+
+#[test]
+fn unsafe_impls_are_rendered_as_unsafe() {
+    let lib = rustdoc_json_for_lib(
+        r#"
+pub struct Safe(pub u8);
+pub struct Unsafe(*const u8);
+unsafe impl Send for Unsafe {}
+        "#,
+    );
+
+    let items = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap()
+        .into_items()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(items.contains(&String::from("impl core::marker::Send for lib::Safe")));
+    assert!(items.contains(&String::from(
+        "unsafe impl core::marker::Send for lib::Unsafe"
+    )));
+    assert!(!items.contains(&String::from("impl core::marker::Send for lib::Unsafe")));
+}
+
+#[test]
+fn blanket_impl_policy() {
+    let lib = rustdoc_json_for_lib(
+        "pub trait Ext { fn ext(&self) {} } impl<T> Ext for T {} pub struct S;",
+    );
+
+    let items = |policy| -> Vec<String> {
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .blanket_impl_policy(policy)
+            .build()
+            .unwrap()
+            .into_items()
+            .map(|item| item.to_string())
+            .collect()
+    };
+    let has_local = |items: &[String]| items.iter().any(|i| i == "impl<T> lib::Ext for lib::S");
+    let has_external = |items: &[String]| {
+        items
+            .iter()
+            .any(|i| i.starts_with("impl<T> core::any::Any for lib::S"))
+    };
+
+    let all = items(BlanketImplPolicy::All);
+    assert!(has_local(&all) && has_external(&all));
+
+    let local_only = items(BlanketImplPolicy::LocalOnly);
+    assert!(has_local(&local_only) && !has_external(&local_only));
+
+    let none = items(BlanketImplPolicy::None);
+    assert!(!has_local(&none) && !has_external(&none));
+}
+
+#[test]
+fn strict() {
+    let lib = rustdoc_json_for_lib("pub mod m { pub fn f() {} }");
+    let builder = public_api::Builder::from_rustdoc_json(&lib.json_path).strict(true);
+    assert!(builder.build().is_ok());
+
+    // Make `f` go missing from the rustdoc JSON, like items sometimes do
+    let json = fs::read_to_string(&lib.json_path).unwrap();
+    let mut crate_: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let index = crate_["index"].as_object_mut().unwrap();
+    let f_id = index
+        .iter()
+        .find(|(_, item)| item["name"] == "f")
+        .map(|(id, _)| id.clone())
+        .unwrap();
+    index.remove(&f_id);
+    fs::write(&lib.json_path, crate_.to_string()).unwrap();
+
+    let lenient = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .build()
+        .unwrap();
+    assert_eq!(lenient.missing_item_ids().collect::<Vec<_>>(), [&f_id]);
+
+    let result = public_api::Builder::from_rustdoc_json(&lib.json_path)
+        .strict(true)
+        .build();
+    assert!(matches!(
+        result,
+        Err(Error::UnsupportedConstruct { location, .. }) if location == f_id
+    ));
+}
+
+#[test]
+fn diagnostic_attrs_on_traits_are_opt_in() {
+    let json = rustdoc_json_for_lib(
+        r#"
+#![allow(stable_features)]
+#![feature(diagnostic_namespace)]
+#[diagnostic::on_unimplemented(message = "not a Trait")]
+pub trait Trait {}
+        "#,
+    );
+    let render = |include_diagnostic_attrs| {
+        public_api::Builder::from_rustdoc_json(&json.json_path)
+            .include_diagnostic_attrs(include_diagnostic_attrs)
+            .build()
+            .unwrap()
+            .items()
+            .map(ToString::to_string)
+            .filter(|item| item.contains("lib::Trait"))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(render(false), vec!["pub trait lib::Trait"]);
+    assert_eq!(
+        render(true),
+        vec![r#"#[diagnostic::on_unimplemented(message = "not a Trait")] pub trait lib::Trait"#]
+    );
+}
+
+#[test]
+fn associated_type_bounds() {
+    let json = rustdoc_json_for_lib(
+        r#"
+#![allow(stable_features)]
+#![feature(associated_type_bounds)]
+use std::fmt::Display;
+pub fn items() -> impl Iterator<Item: Display + Clone> {
+    std::iter::empty::<u8>()
+}
+        "#,
+    );
+    let api = public_api::Builder::from_rustdoc_json(&json.json_path)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        api.items().map(ToString::to_string).collect::<Vec<_>>(),
+        vec![
+            "pub mod lib",
+            "pub fn lib::items() -> impl core::iter::traits::iterator::Iterator<Item: core::fmt::Display + core::clone::Clone>",
+        ]
+    );
+}
+
+#[test]
+fn include_spans() {
+    let json = rustdoc_json_for_lib("pub struct S;\n\npub fn f() {}\n");
+    let span_of_f = |include_spans| {
+        public_api::Builder::from_rustdoc_json(&json.json_path)
+            .include_spans(include_spans)
+            .build()
+            .unwrap()
+            .items()
+            .filter(|item| item.to_string() == "pub fn lib::f()")
+            .map(|item| item.span().map(|(file, line)| (file.to_owned(), line)))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(span_of_f(false), vec![None]);
+    assert_eq!(span_of_f(true), vec![Some((PathBuf::from("lib.rs"), 3))]);
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn line_filter() {
+    let old = rustdoc_json_for_lib("pub fn f() {} pub struct S;");
+    let new = rustdoc_json_for_lib("pub fn f(x: u8) {} pub struct S(pub u8);");
+    let build = |lib: &LibWithJson| {
+        public_api::Builder::from_rustdoc_json(&lib.json_path)
+            .line_filter(Some(regex::Regex::new("^pub fn ").unwrap()))
+            .build()
+            .unwrap()
+    };
+
+    let listed = build(&new)
+        .items()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(listed, vec!["pub fn lib::f(x: u8)"]);
+
+    let diff = PublicApiDiff::between(build(&old), build(&new));
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+}
+
 #[test]
 fn invalid_json() {
     let invalid_json = NamedTempFile::new().unwrap();
     write!(invalid_json.as_file(), "}}}}}}}}}}").unwrap();
     let result = public_api::Builder::from_rustdoc_json(invalid_json.path()).build();
     assert!(matches!(result, Err(Error::SerdeJsonError(_))));
+
+    let error = result.unwrap_err();
+    let message = error.to_string();
+    assert!(message.starts_with("Failed to parse rustdoc JSON: the input is not valid JSON"));
+    assert!(!message.contains("column"));
+    assert!(!message.contains("nightly"));
+    let source = std::error::Error::source(&error).unwrap();
+    assert!(source.is::<serde_json::Error>());
 }
 
 struct LibWithJson {
@@ -246,6 +1434,24 @@ fn rustdoc_json_for_lib(lib: &str) -> LibWithJson {
     }
 }
 
+fn semver_impact_between_libs(old_lib: &str, new_lib: &str) -> SemverImpact {
+    diff_between_libs(old_lib, new_lib).semver_impact()
+}
+
+fn diff_between_libs(old_lib: &str, new_lib: &str) -> PublicApiDiff {
+    let old_json = rustdoc_json_for_lib(old_lib);
+    let new_json = rustdoc_json_for_lib(new_lib);
+
+    let old = public_api::Builder::from_rustdoc_json(old_json.json_path)
+        .build()
+        .unwrap();
+    let new = public_api::Builder::from_rustdoc_json(new_json.json_path)
+        .build()
+        .unwrap();
+
+    PublicApiDiff::between(old, new)
+}
+
 fn assert_public_api_diff(
     old_json: impl Into<PathBuf>,
     new_json: impl Into<PathBuf>,
@@ -18,9 +18,18 @@ pub fn doc_hidden() {}
 #[export_name = "something_arbitrary"]
 pub fn export_name() {}
 
-// #[must_use] is not shown by cargo doc, so we should not display it either if
-// it is present
+#[no_mangle]
+pub extern "C" fn no_mangle_extern_c() {}
+
 #[must_use]
 pub fn must_use() -> usize {
     0
 }
+
+#[must_use = "the answer is important"]
+pub fn must_use_with_message() -> usize {
+    42
+}
+
+#[deprecated(since = "1.0.0", note = "Use `must_use()` instead")]
+pub fn deprecated() {}
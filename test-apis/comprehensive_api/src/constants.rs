@@ -1 +1,5 @@
 pub const CONST: &str = "const";
+
+pub const SUFFIXED: u8 = 5u8;
+
+pub const UNSUFFIXED: u8 = 5;
@@ -18,6 +18,11 @@ pub enum DiverseVariants {
     Recursive { child: Box<DiverseVariants> },
 }
 
+pub enum EnumWithGenericTupleVariant<T> {
+    Value(T),
+    Empty,
+}
+
 pub enum EnumWithGenerics<'a, T, D: Debug>
 where
     T: Display,
@@ -25,6 +30,15 @@ where
     Variant { t: &'a T, d: D },
 }
 
+pub enum EnumWithStrippedStructVariants {
+    Empty {},
+    Partial {
+        visible: usize,
+        #[doc(hidden)]
+        hidden: bool,
+    },
+}
+
 pub enum EnumWithStrippedTupleVariants {
     Single(usize),
     SingleHidden(#[doc(hidden)] usize),
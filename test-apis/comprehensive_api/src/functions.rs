@@ -11,6 +11,8 @@ pub fn plain() {}
 
 pub const fn const_fn() {}
 
+pub const unsafe fn const_unsafe_fn() {}
+
 pub fn one_arg(x: usize) {
     println!("{}", x);
 }
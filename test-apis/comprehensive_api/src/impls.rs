@@ -1,3 +1,5 @@
+use std::{pin::Pin, rc::Rc};
+
 use crate::{
     structs::{Plain, Unit, WithLifetimeAndGenericParam},
     traits::{
@@ -17,6 +19,12 @@ impl Plain {
     pub fn s2(&self) {}
 
     pub fn s3(&mut self) {}
+
+    pub fn consume(self: Box<Self>) {}
+
+    pub fn shared(self: Rc<Self>) {}
+
+    pub fn pinned(self: Pin<&mut Self>) {}
 }
 
 impl<'a> Plain {
@@ -4,3 +4,5 @@ macro_rules! simple_macro {
         println!("simple_macro with {}", format!($($arg)*));
     })
 }
+
+pub use crate::simple_macro;
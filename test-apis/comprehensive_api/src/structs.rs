@@ -24,6 +24,13 @@ pub struct ConstArg<T, const N: usize> {
     pub items: [T; N],
 }
 
+pub struct ConstArgWithDefault<const N: usize = 8>
+where
+    [(); N]: Sized,
+{
+    pub items: [u8; N],
+}
+
 pub struct WithTraitBounds<T: Display + Debug> {
     t: T,
 }
@@ -43,6 +43,8 @@ pub trait TraitWithBounds: private_mod::PubTraitInPrivateMod + Simple + Send {}
 
 pub trait TraitWithBoundsAndGenerics<U>: Simple {}
 
+pub trait Supertraits: Clone + Simple {}
+
 mod private_mod {
     pub trait PubTraitInPrivateMod {}
 }
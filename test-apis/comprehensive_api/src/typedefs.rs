@@ -3,3 +3,9 @@ use crate::structs::Plain;
 pub type TypedefPlain = Plain;
 
 pub type RedefinedResult<T, E> = Result<T, E>;
+
+#[allow(type_alias_bounds)]
+pub type WhereClause<T>
+where
+    T: Clone,
+= Vec<T>;
@@ -33,7 +33,10 @@
 //!
 //! [1]: https://github.com/rust-lang/rust/pull/109410
 
+#![feature(extern_types)]
 #![feature(inherent_associated_types)]
+#![allow(incomplete_features)]
+#![feature(specialization)]
 
 pub struct StructWithInherentAssociatedType;
 
@@ -44,3 +47,19 @@ impl StructWithInherentAssociatedType {
         1
     }
 }
+
+extern "C" {
+    /// An opaque FFI handle. Renders as `pub extern type nightly_api::Handle`
+    /// so that it can't be mistaken for a type alias.
+    pub type Handle;
+}
+
+pub trait Specializable {
+    fn specializable(&self);
+}
+
+impl<T> Specializable for T {
+    /// Should render as `pub default fn`, but rustdoc JSON does not tell
+    /// that this fn is `default`, so for now it renders as `pub fn`.
+    default fn specializable(&self) {}
+}